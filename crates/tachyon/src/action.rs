@@ -1,16 +1,20 @@
 //! Tachyon Action descriptions.
 
 use alloc::vec::Vec;
-use core::{cmp, cmp::Ord, marker::PhantomData};
+use core::{cmp, cmp::Ord, marker::PhantomData, ops::Range};
 
 use corez::io::{self, Read, Write};
-use derive_more::{Debug, Display, Eq as TotalEq, PartialEq};
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
 use pasta_curves::{EpAffine, group::GroupEncoding as _};
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
 
 use crate::{
+    bundle,
     entropy::{ActionEntropy, ActionRandomizer},
-    keys::{private, public},
-    note::Note,
+    keys::{SpendValidatingKey, private, public},
+    note::{self, Note},
     primitives::{ActionDigest, ActionDigestError, Effect, effect},
     reddsa, serialization, value,
 };
@@ -27,6 +31,9 @@ pub struct Descriptor {
 }
 
 impl Descriptor {
+    /// Encoded length of a descriptor: `cv || rk`.
+    pub const SIZE: usize = 64;
+
     /// Derive the action digest.
     pub fn digest(&self) -> Result<ActionDigest, ActionDigestError> {
         ActionDigest::new(self.cv, self.rk)
@@ -45,6 +52,75 @@ impl Descriptor {
         serialization::write_action_vk(&mut writer, &self.rk.0)?;
         Ok(())
     }
+
+    /// Check that this descriptor is the spend described by a witness.
+    ///
+    /// Recomputes $\mathsf{cv}$ from the note value and `rcv`, and
+    /// $\mathsf{rk} = \mathsf{ak} + [\alpha]\,\mathcal{G}$. A prover handed
+    /// an externally-signed action learns of a mismatch here, rather than as
+    /// an opaque proof failure.
+    ///
+    /// The spend's tachygrams are not checked: the circuit derives them from
+    /// the note and the nullifier delegation, not from the descriptor.
+    pub fn check_spend(
+        &self,
+        ak: &SpendValidatingKey,
+        alpha: &ActionRandomizer<effect::Spend>,
+        note: &Note,
+        rcv: value::Trapdoor,
+    ) -> Result<(), WitnessMismatch> {
+        if self.cv != effect::Spend::commit_value(rcv, note.value) {
+            return Err(WitnessMismatch::ValueCommitment);
+        }
+        if self.rk != ak.derive_action_public(alpha) {
+            return Err(WitnessMismatch::RandomizedKey);
+        }
+        Ok(())
+    }
+
+    /// Check that this descriptor is the output described by a witness.
+    ///
+    /// Recomputes $\mathsf{cv}$ from the note value and `rcv`, and
+    /// $\mathsf{rk} = [\alpha]\,\mathcal{G}$. The output's tachygram is the
+    /// note commitment, derived from the note inside the circuit.
+    pub fn check_output(
+        &self,
+        alpha: &ActionRandomizer<effect::Output>,
+        note: &Note,
+        rcv: value::Trapdoor,
+    ) -> Result<(), WitnessMismatch> {
+        if self.cv != effect::Output::commit_value(rcv, note.value) {
+            return Err(WitnessMismatch::ValueCommitment);
+        }
+        if self.rk != private::ActionSigningKey::new(alpha).derive_action_public() {
+            return Err(WitnessMismatch::RandomizedKey);
+        }
+        Ok(())
+    }
+}
+
+/// A descriptor field that disagrees with the witness it is paired with.
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+pub enum WitnessMismatch {
+    /// `cv` does not commit to the note value under `rcv`.
+    #[display("value commitment does not match note value and trapdoor")]
+    ValueCommitment,
+    /// `rk` is not the expected key randomized by alpha.
+    #[display("randomized key does not match key and randomizer")]
+    RandomizedKey,
+}
+
+/// Error padding a [`GroupBuilder`]'s actions.
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+pub enum PaddingError {
+    /// The group needs padding but has no change to split into it.
+    #[display("padding needs change to split")]
+    NoChange,
+    /// The change is worth less than one zatoshi per output it must fill.
+    #[display("change too small to split across the padding")]
+    ChangeTooSmall,
 }
 
 impl PartialOrd for Descriptor {
@@ -117,6 +193,25 @@ impl Plan<effect::Spend> {
             _effect: PhantomData,
         }
     }
+
+    /// Assemble many spend action plans, as by [`Plan::spend`].
+    ///
+    /// Note commitment, alpha derivation, and `rk` derivation are independent
+    /// per action. With the `rayon` feature they run in parallel. The result
+    /// is in the same order as `entries`.
+    #[must_use]
+    pub fn spend_batch(
+        entries: Vec<(Note, ActionEntropy, value::Trapdoor)>,
+        derive_rk: impl Fn(ActionRandomizer<effect::Spend>) -> public::ActionVerificationKey + Sync,
+    ) -> Vec<Self> {
+        #[cfg(feature = "rayon")]
+        let iter = entries.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let iter = entries.into_iter();
+
+        iter.map(|(note, theta, rcv)| Self::spend(note, theta, rcv, &derive_rk))
+            .collect()
+    }
 }
 
 impl Plan<effect::Output> {
@@ -137,6 +232,21 @@ impl Plan<effect::Output> {
             _effect: PhantomData,
         }
     }
+
+    /// Assemble many output action plans, as by [`Plan::output`].
+    ///
+    /// With the `rayon` feature, actions are assembled in parallel. The result
+    /// is in the same order as `entries`.
+    #[must_use]
+    pub fn output_batch(entries: Vec<(Note, ActionEntropy, value::Trapdoor)>) -> Vec<Self> {
+        #[cfg(feature = "rayon")]
+        let iter = entries.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let iter = entries.into_iter();
+
+        iter.map(|(note, theta, rcv)| Self::output(note, theta, rcv))
+            .collect()
+    }
 }
 
 impl<E: Effect> Plan<E> {
@@ -161,6 +271,225 @@ impl<E: Effect> Plan<E> {
             rk: self.rk,
         }
     }
+
+    /// Read an action plan `rk || note || theta || rcv`.
+    ///
+    /// The plan carries the note and its trapdoors, so this is not a
+    /// consensus encoding. `rk` is read as given; a prover checks it against
+    /// the witness with [`Descriptor::check_spend`] or
+    /// [`Descriptor::check_output`].
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let rk = public::ActionVerificationKey(serialization::read_action_vk(&mut reader)?);
+        let note = Note::read(&mut reader)?;
+        let mut theta = [0u8; 32];
+        reader.read_exact(&mut theta)?;
+        let rcv = value::Trapdoor::read(&mut reader)?;
+        Ok(Self {
+            rk,
+            note,
+            theta: ActionEntropy::from_bytes(theta),
+            rcv,
+            _effect: PhantomData,
+        })
+    }
+
+    /// Write an action plan `rk || note || theta || rcv`.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_action_vk(&mut writer, &self.rk.0)?;
+        self.note.write(&mut writer)?;
+        writer.write_all(&self.theta.0)?;
+        self.rcv.write(&mut writer)
+    }
+}
+
+/// Collects action plans and pads them to a uniform shape.
+///
+/// [`build`](Self::build) pads until the total action count is a power of
+/// two. Uniform counts keep the stamp merge tree balanced and stop the action
+/// count from revealing how many payments a bundle makes.
+///
+/// Padding moves no value: the [change](Self::add_change) is split into as
+/// many outputs to the change key as the shape needs. There is no zero-value
+/// padding output, since the output step rejects zero-value notes, so a group
+/// that needs padding must carry change worth at least one zatoshi for each
+/// output it is split into.
+#[derive(Clone, Debug, Default)]
+pub struct GroupBuilder {
+    spends: Vec<Plan<effect::Spend>>,
+    outputs: Vec<Plan<effect::Output>>,
+    change: Option<Note>,
+}
+
+impl GroupBuilder {
+    /// Start an empty group.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            spends: Vec::new(),
+            outputs: Vec::new(),
+            change: None,
+        }
+    }
+
+    /// Add a spend action plan.
+    pub fn add_spend(&mut self, plan: Plan<effect::Spend>) -> &mut Self {
+        self.spends.push(plan);
+        self
+    }
+
+    /// Add an output action plan.
+    pub fn add_output(&mut self, plan: Plan<effect::Output>) -> &mut Self {
+        self.outputs.push(plan);
+        self
+    }
+
+    /// Return `note` as change, split across the padding. Replaces any
+    /// earlier change.
+    ///
+    /// Each piece keeps the note's payment key and takes fresh trapdoors.
+    pub const fn add_change(&mut self, note: Note) -> &mut Self {
+        self.change = Some(note);
+        self
+    }
+
+    /// Pad to the next power-of-two action count, counting the change as one
+    /// action.
+    ///
+    /// # Errors
+    ///
+    /// As [`build_to`](Self::build_to).
+    pub fn build<RNG: RngCore + CryptoRng>(self, rng: &mut RNG) -> Result<Group, PaddingError> {
+        let target = (self.spends.len() + self.outputs.len() + usize::from(self.change.is_some()))
+            .next_power_of_two();
+        self.build_to(rng, target)
+    }
+
+    /// Pad to `target` actions, as chosen by a [`bundle::Policy`]. The change,
+    /// if any, always takes at least one output, so no padding is added if
+    /// the group already holds `target` actions or more.
+    ///
+    /// # Errors
+    ///
+    /// [`PaddingError::NoChange`] if padding is needed and there is no change,
+    /// and [`PaddingError::ChangeTooSmall`] if the change cannot give each of
+    /// its outputs a zatoshi.
+    pub fn build_to<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        target: usize,
+    ) -> Result<Group, PaddingError> {
+        let Self {
+            spends,
+            mut outputs,
+            change,
+        } = self;
+        let real = spends.len() + outputs.len();
+
+        let first_change = outputs.len();
+        if let Some(note) = change {
+            let pieces = target.saturating_sub(real).max(1);
+            for value in split_value(note.value, pieces)? {
+                let piece = Note {
+                    value,
+                    psi: note::NullifierTrapdoor::random(rng),
+                    rcm: note::CommitmentTrapdoor::random(rng),
+                    ..note
+                };
+                outputs.push(Plan::output(
+                    piece,
+                    ActionEntropy::random(rng),
+                    value::Trapdoor::random(rng),
+                ));
+            }
+        } else if target > real {
+            return Err(PaddingError::NoChange);
+        }
+        let change = first_change..outputs.len();
+
+        Ok(Group {
+            spends,
+            outputs,
+            change,
+        })
+    }
+}
+
+/// Split `total` into `count` values as even as possible, each at least one.
+fn split_value(total: value::Positive, count: usize) -> Result<Vec<value::Positive>, PaddingError> {
+    let total = u64::from(total);
+    let count = u64::try_from(count).map_err(|_err| PaddingError::ChangeTooSmall)?;
+    let share = total.checked_div(count).unwrap_or_default();
+    let extra = total.checked_rem(count).unwrap_or_default();
+    (0..count)
+        .map(|index| {
+            value::Positive::try_from(share + u64::from(index < extra))
+                .map_err(|_err| PaddingError::ChangeTooSmall)
+        })
+        .collect()
+}
+
+/// Action plans padded to a power-of-two count by [`GroupBuilder`].
+///
+/// Which outputs are padding is known only to the builder's holder; the
+/// change pieces are ordinary outputs on the wire.
+#[derive(Clone, Debug)]
+pub struct Group {
+    spends: Vec<Plan<effect::Spend>>,
+    outputs: Vec<Plan<effect::Output>>,
+    change: Range<usize>,
+}
+
+impl Group {
+    /// Total number of actions, including padding.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.spends.len() + self.outputs.len()
+    }
+
+    /// Whether the group holds no actions. Never true for a built group.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of outputs added as padding: the change pieces after the first.
+    #[must_use]
+    pub const fn padding_len(&self) -> usize {
+        self.change
+            .end
+            .saturating_sub(self.change.start)
+            .saturating_sub(1)
+    }
+
+    /// Whether the output at `index` is padding.
+    #[must_use]
+    pub const fn is_padding(&self, index: usize) -> bool {
+        index > self.change.start && index < self.change.end
+    }
+
+    /// The spend plans.
+    #[must_use]
+    pub const fn spends(&self) -> &[Plan<effect::Spend>] {
+        self.spends.as_slice()
+    }
+
+    /// The output plans, change last.
+    #[must_use]
+    pub const fn outputs(&self) -> &[Plan<effect::Output>] {
+        self.outputs.as_slice()
+    }
+
+    /// The change output plans, whose notes the wallet keeps.
+    #[must_use]
+    pub fn change(&self) -> &[Plan<effect::Output>] {
+        self.outputs.get(self.change.clone()).unwrap_or_default()
+    }
+}
+
+impl From<Group> for bundle::Plan {
+    fn from(group: Group) -> Self {
+        Self::new(group.spends, group.outputs)
+    }
 }
 
 /// An authorized Tachyon action.
@@ -193,11 +522,30 @@ impl From<(Descriptor, Signature)> for Action {
 }
 
 impl Action {
+    /// Encoded length of an action: `cv || rk || sig`.
+    pub const SIZE: usize = Descriptor::SIZE + Signature::SIZE;
+
     /// Derive the action digest.
     pub fn digest(&self) -> Result<ActionDigest, ActionDigestError> {
         ActionDigest::new(self.cv, self.rk)
     }
 
+    /// Read a standalone action `cv || rk || sig`.
+    ///
+    /// Bundles do not use this layout: they write all descriptors before all
+    /// signatures. This is the encoding for a single action outside a bundle.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let desc = Descriptor::read(&mut reader)?;
+        let sig = Signature::read(&mut reader)?;
+        Ok(Self::from((desc, sig)))
+    }
+
+    /// Write a standalone action `cv || rk || sig`.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.descriptor().write(&mut writer)?;
+        self.sig.write(&mut writer)
+    }
+
     /// Obtain a descriptor for this action.
     #[must_use]
     pub const fn descriptor(&self) -> Descriptor {
@@ -251,6 +599,9 @@ impl Ord for Signature {
 }
 
 impl Signature {
+    /// Encoded length of an action signature.
+    pub const SIZE: usize = 64;
+
     /// Read an action signature from the consensus wire format.
     pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
         let sig = serialization::read_action_sig(&mut reader)?;
@@ -284,3 +635,210 @@ impl FromIterator<Signature> for Vec<[u8; 64]> {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+    use crate::fixtures::{WalletSim, build_output_plan, random_action};
+
+    /// Descriptor checks recompute `cv` and `rk` from the witness.
+    #[test]
+    fn descriptor_checks_against_witness() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::random(rng);
+        let ak = wallet.pak.ak;
+
+        let note = wallet.random_note(300);
+        let theta = ActionEntropy::random(rng);
+        let rcv = value::Trapdoor::random(rng);
+        let spend_alpha = theta.randomizer::<effect::Spend>(note.commitment());
+        let output_alpha = theta.randomizer::<effect::Output>(note.commitment());
+
+        let spend = Plan::spend(note, theta, rcv, |alpha| ak.derive_action_public(&alpha));
+        let output = Plan::output(note, theta, rcv);
+
+        let spend_desc = spend.descriptor();
+        let output_desc = output.descriptor();
+        assert_eq!(
+            spend_desc.check_spend(&ak, &spend_alpha, &note, rcv),
+            Ok(())
+        );
+        assert_eq!(output_desc.check_output(&output_alpha, &note, rcv), Ok(()));
+
+        let other_rcv = value::Trapdoor::random(rng);
+        assert_eq!(
+            spend_desc.check_spend(&ak, &spend_alpha, &note, other_rcv),
+            Err(WitnessMismatch::ValueCommitment)
+        );
+        assert_eq!(
+            output_desc.check_output(&output_alpha, &note, other_rcv),
+            Err(WitnessMismatch::ValueCommitment)
+        );
+
+        let other_ak = WalletSim::random(rng).pak.ak;
+        assert_eq!(
+            spend_desc.check_spend(&other_ak, &spend_alpha, &note, rcv),
+            Err(WitnessMismatch::RandomizedKey)
+        );
+        let other_alpha =
+            ActionEntropy::random(rng).randomizer::<effect::Output>(note.commitment());
+        assert_eq!(
+            output_desc.check_output(&other_alpha, &note, rcv),
+            Err(WitnessMismatch::RandomizedKey)
+        );
+    }
+
+    /// Batch assembly matches one-at-a-time assembly, in input order.
+    #[test]
+    fn batch_matches_individual_plans() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::random(rng);
+        let ask = wallet.sk.derive_auth_private();
+        let derive_rk = |alpha: ActionRandomizer<effect::Spend>| {
+            ask.derive_action_private(&alpha).derive_action_public()
+        };
+
+        let entries: Vec<_> = (1..=4u64)
+            .map(|amount| {
+                (
+                    wallet.random_note(amount),
+                    ActionEntropy::random(&mut *rng),
+                    value::Trapdoor::random(&mut *rng),
+                )
+            })
+            .collect();
+
+        let spends = Plan::spend_batch(entries.clone(), derive_rk);
+        let outputs = Plan::output_batch(entries.clone());
+        assert_eq!(spends.len(), entries.len());
+        assert_eq!(outputs.len(), entries.len());
+
+        for ((entry, spend), output) in entries.into_iter().zip(spends).zip(outputs) {
+            let (note, theta, rcv) = entry;
+            assert_eq!(spend, Plan::spend(note, theta, rcv, derive_rk));
+            assert_eq!(output, Plan::output(note, theta, rcv));
+        }
+    }
+
+    /// Padding splits the change, so the group's outputs keep its value.
+    #[test]
+    fn group_pads_to_power_of_two() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::random(rng);
+        let change = wallet.random_note(100);
+
+        for (n_outputs, expected) in [(0, 1), (1, 2), (3, 4), (4, 8), (5, 8)] {
+            let mut builder = GroupBuilder::new();
+            for _ in 0..n_outputs {
+                let (_, _, plan) = build_output_plan(rng, wallet.random_note(100));
+                builder.add_output(plan);
+            }
+            let group = builder.add_change(change).clone().build(rng).unwrap();
+
+            assert_eq!(group.len(), expected, "{n_outputs} outputs");
+            assert_eq!(group.padding_len(), expected - n_outputs - 1);
+            for index in 0..expected {
+                assert_eq!(group.is_padding(index), index > n_outputs);
+            }
+            assert_eq!(
+                group
+                    .change()
+                    .iter()
+                    .map(|plan| u64::from(plan.note.value))
+                    .sum::<u64>(),
+                100,
+                "change pieces keep the change value"
+            );
+            assert!(
+                group.change().iter().all(|plan| plan.note.pk.0 == change.pk.0),
+                "change pieces pay the change key"
+            );
+        }
+    }
+
+    /// Padding without change, or with too little to split, is an error.
+    #[test]
+    fn group_padding_needs_enough_change() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::random(rng);
+
+        let mut builder = GroupBuilder::new();
+        for _ in 0..3 {
+            let (_, _, plan) = build_output_plan(rng, wallet.random_note(100));
+            builder.add_output(plan);
+        }
+        assert_eq!(
+            builder.clone().build_to(rng, 4).unwrap_err(),
+            PaddingError::NoChange
+        );
+        builder.add_change(wallet.random_note(1));
+        assert_eq!(
+            builder.clone().build_to(rng, 8).unwrap_err(),
+            PaddingError::ChangeTooSmall
+        );
+        assert_eq!(builder.build_to(rng, 4).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn action_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let action = random_action(rng);
+
+        let mut bytes = Vec::new();
+        action.write(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), Action::SIZE);
+
+        let decoded = Action::read(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, action);
+    }
+
+    #[test]
+    fn action_read_rejects_truncated() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let action = random_action(rng);
+
+        let mut bytes = Vec::new();
+        action.write(&mut bytes).unwrap();
+        bytes.pop();
+
+        Action::read(bytes.as_slice()).unwrap_err();
+    }
+
+    /// An `x` coordinate at or above the field modulus is not a canonical
+    /// encoding, even if it would reduce to a valid point.
+    #[test]
+    fn descriptor_read_rejects_unreduced_cv() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let action = random_action(rng);
+
+        let mut bytes = Vec::new();
+        action.descriptor().write(&mut bytes).unwrap();
+        bytes[..32].copy_from_slice(&[0xFF; 32]);
+
+        Descriptor::read(bytes.as_slice()).unwrap_err();
+    }
+
+    /// A signature is rejected at parse time if `R` is not a canonical point
+    /// or `s` is not a canonical scalar.
+    #[test]
+    fn signature_read_rejects_noncanonical() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let action = random_action(rng);
+
+        let mut bytes = Vec::new();
+        action.sig.write(&mut bytes).unwrap();
+        assert_eq!(Signature::read(bytes.as_slice()).unwrap(), action.sig);
+
+        let mut bad_r = bytes.clone();
+        bad_r[..32].copy_from_slice(&[0xFF; 32]);
+        Signature::read(bad_r.as_slice()).unwrap_err();
+
+        let mut bad_s = bytes;
+        bad_s[32..].copy_from_slice(&[0xFF; 32]);
+        Signature::read(bad_s.as_slice()).unwrap_err();
+    }
+}
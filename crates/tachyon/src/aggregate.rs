@@ -0,0 +1,971 @@
+//! Aggregates: one proof-stamped bundle covering the actions of its adjuncts.
+//!
+//! An aggregator takes autonomes, bundles whose stamps cover only their own
+//! actions, and merges their stamps into one. The bundle that keeps the merged
+//! stamp is the aggregate's own; the others are stripped to adjuncts, which
+//! point to the covering transaction by its wtxid.
+
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use core::iter;
+
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    action::{self, Action},
+    bundle::{self, Bundle, VerificationError},
+    keys::public,
+    primitives::{Anchor, Tachygram},
+    stamp::{self, PointerStamp, ProofStamp, ProveError, StampState, proof::pool},
+    value,
+};
+
+/// Errors that can occur while merging into an aggregate.
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "intentional name")]
+pub enum AggregateError {
+    /// A bundle covers more than its own actions, so merging it would lose
+    /// its adjuncts.
+    ///
+    /// The index is where the bundle would have stood in the aggregate: the
+    /// number of bundles accepted before it, counting the aggregate's own.
+    #[display("bundle {_0} is not an autonome")]
+    NotAutonome(#[error(not(source))] usize),
+    /// Merging the stamps failed.
+    #[display("stamp merge failed: {_0}")]
+    Merge(ProveError),
+    /// The aggregate would carry more actions than its limit.
+    #[display("too many actions")]
+    TooManyActions,
+    /// The merged stamp would carry more tachygrams than its limit.
+    #[display("too many tachygrams")]
+    TooManyTachygrams,
+    /// The merged stamp would fold more merges than its limit.
+    #[display("merge depth exceeded")]
+    TooDeep,
+    /// Two stamps publish this tachygram, so their merge cannot be proven.
+    #[display("stamps share tachygram {_0:?}")]
+    TachygramCollision(#[error(not(source))] Tachygram),
+}
+
+/// Limits an [`Aggregator`] holds its aggregate to.
+///
+/// The default imposes none.
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub struct Limits {
+    /// Most actions across the bundle and its adjuncts.
+    pub max_actions: usize,
+    /// Most tachygrams on the merged stamp.
+    pub max_tachygrams: usize,
+    /// Most merges folded into the merged stamp.
+    ///
+    /// The aggregator folds each accepted bundle into the running stamp, so
+    /// every bundle after the first adds one level of proof recursion. Depth
+    /// within each bundle's own stamp is not counted.
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_actions: usize::MAX,
+            max_tachygrams: usize::MAX,
+            max_depth: usize::MAX,
+        }
+    }
+}
+
+/// A proof-stamped bundle and the adjuncts its stamp covers.
+///
+/// The aggregate's transaction carries the bundle; each adjunct lands in its
+/// own transaction, pointing to the aggregate's wtxid.
+///
+/// Adjuncts are kept in canonical order, by [`Bundle::commitment`], so the
+/// same bundles aggregate to the same adjunct list whatever order they were
+/// merged in. The commitment does not cover the stamp, so re-pointing
+/// adjuncts never reorders them. The merged proof itself is randomized and
+/// differs between provers.
+#[derive(Clone, Debug)]
+pub struct Aggregate {
+    bundle: Bundle<ProofStamp>,
+    adjuncts: Vec<Bundle<PointerStamp>>,
+}
+
+impl Aggregate {
+    /// Assemble an aggregate from its parts, as received.
+    ///
+    /// Nothing is checked, and adjuncts keep the order given: see
+    /// [`verify`](Self::verify) and [`normalize`](Self::normalize). An
+    /// autonome is an aggregate with no adjuncts.
+    #[must_use]
+    pub const fn new(bundle: Bundle<ProofStamp>, adjuncts: Vec<Bundle<PointerStamp>>) -> Self {
+        Self { bundle, adjuncts }
+    }
+
+    /// Merge autonomes into this aggregate.
+    ///
+    /// Each autonome's stamp is merged into the aggregate's, and the autonome
+    /// is stripped to an adjunct. Merging changes the aggregate's stamp and so
+    /// its wtxid: `compute_wtxid` maps the merged bundle to the wtxid of the
+    /// transaction carrying it, and every adjunct, old and new, is pointed
+    /// there.
+    ///
+    /// # Errors
+    ///
+    /// Returns the aggregate and every autonome intact if an autonome is
+    /// itself an aggregate, if two stamps share a tachygram, or if the stamps
+    /// do not merge, as when they share an action. Autonomes and shared
+    /// tachygrams are checked before any proving.
+    pub fn merge<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        autonomes: impl IntoIterator<Item = Bundle<ProofStamp>>,
+        compute_wtxid: impl FnOnce(&Bundle<ProofStamp>) -> PointerStamp,
+    ) -> Result<Self, Box<MergeRejected>> {
+        let autonomes: Vec<Bundle<ProofStamp>> = autonomes.into_iter().collect();
+        let stamp = match self.merged_stamp(rng, &autonomes) {
+            Ok(stamp) => stamp,
+            Err(error) => {
+                return Err(Box::new(MergeRejected {
+                    aggregate: self,
+                    autonomes,
+                    error,
+                }));
+            },
+        };
+
+        let bundle = Bundle {
+            stamp,
+            ..self.bundle
+        };
+        let wtxid = compute_wtxid(&bundle);
+        let adjuncts = self
+            .adjuncts
+            .into_iter()
+            .map(|adjunct| adjunct.map_stamp(|_stamp| wtxid))
+            .chain(
+                autonomes
+                    .into_iter()
+                    .map(|autonome| Stripped::split(autonome).0.point(wtxid)),
+            )
+            .collect();
+
+        let mut aggregate = Self { bundle, adjuncts };
+        aggregate.normalize();
+        Ok(aggregate)
+    }
+
+    /// The aggregate's stamp with those of `autonomes` folded in, in order.
+    ///
+    /// Every autonome is screened before the first merge is proven.
+    fn merged_stamp<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        autonomes: &[Bundle<ProofStamp>],
+    ) -> Result<ProofStamp, AggregateError> {
+        let accepted = 1 + self.adjuncts.len();
+        if let Some(position) = autonomes
+            .iter()
+            .position(|autonome| !autonome.is_autonome())
+        {
+            return Err(AggregateError::NotAutonome(accepted + position));
+        }
+        if let Some(&tachygram) = stamp::duplicate_tachygrams(
+            iter::once(&self.bundle.stamp).chain(autonomes.iter().map(|autonome| &autonome.stamp)),
+        )
+        .first()
+        {
+            return Err(AggregateError::TachygramCollision(tachygram));
+        }
+
+        autonomes
+            .iter()
+            .try_fold(
+                (self.bundle.stamp.clone(), self.covered()),
+                |mut running, autonome| {
+                    running.0 = fold(rng, running.0, &running.1, autonome, &[])?;
+                    running.1.extend(autonome.descriptors());
+                    Ok(running)
+                },
+            )
+            .map(|running| running.0)
+    }
+
+    /// Descriptors of every action the stamp covers, across the aggregate's
+    /// bundle and its adjuncts.
+    fn covered(&self) -> BTreeSet<action::Descriptor> {
+        self.bundle
+            .descriptors()
+            .into_iter()
+            .chain(self.adjuncts.iter().flat_map(Bundle::descriptors))
+            .collect()
+    }
+
+    /// Merge one more autonome into the aggregate's stamp.
+    ///
+    /// A fold: the existing stamp is merged with the autonome's, not reproven
+    /// from its parts. The autonome is stripped to an adjunct, and, as with
+    /// [`merge`](Self::merge), every adjunct is pointed to the wtxid
+    /// `compute_wtxid` gives for the new stamp.
+    ///
+    /// Merging requires a common anchor, and the merged stamp takes the later
+    /// of the two: whichever stamp is older is lifted over the matching
+    /// segment in `lifts`. An aggregate's anchor therefore never moves back,
+    /// however its autonomes are ordered.
+    ///
+    /// # Errors
+    ///
+    /// Returns the autonome intact, with the aggregate unchanged, if it is
+    /// itself an aggregate, if no segment in `lifts` joins the two anchors, or
+    /// if the stamps do not merge.
+    pub fn push<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+        autonome: Bundle<ProofStamp>,
+        lifts: &[ragu::Pcd<pool::AnchorChain>],
+        compute_wtxid: impl FnOnce(&Bundle<ProofStamp>) -> PointerStamp,
+    ) -> Result<(), Rejected> {
+        let index = 1 + self.adjuncts.len();
+        let folded = if autonome.is_autonome() {
+            fold(
+                rng,
+                self.bundle.stamp.clone(),
+                &self.covered(),
+                &autonome,
+                lifts,
+            )
+        } else {
+            Err(AggregateError::NotAutonome(index))
+        };
+
+        match folded {
+            Ok(stamp) => {
+                self.bundle.stamp = stamp;
+                let wtxid = compute_wtxid(&self.bundle);
+                for adjunct in &mut self.adjuncts {
+                    adjunct.stamp = wtxid;
+                }
+                self.adjuncts.push(Stripped::split(autonome).0.point(wtxid));
+                self.normalize();
+                Ok(())
+            },
+            Err(error) => Err(Rejected {
+                index,
+                bundle: Box::new(autonome),
+                error,
+            }),
+        }
+    }
+
+    /// The aggregate's own bundle, carrying the stamp.
+    #[must_use]
+    pub const fn bundle(&self) -> &Bundle<ProofStamp> {
+        &self.bundle
+    }
+
+    /// The adjuncts the stamp covers, in canonical order once
+    /// [normalized](Self::normalize).
+    #[must_use]
+    pub const fn adjuncts(&self) -> &[Bundle<PointerStamp>] {
+        self.adjuncts.as_slice()
+    }
+
+    /// Put the adjuncts in canonical order, by [`Bundle::commitment`].
+    ///
+    /// [`merge`](Self::merge), [`push`](Self::push), and
+    /// [`Aggregator::seal`] already produce this order; aggregates assembled
+    /// with [`new`](Self::new) keep the order they were given.
+    pub fn normalize(&mut self) {
+        self.adjuncts.sort_by_cached_key(Bundle::commitment);
+    }
+
+    /// Whether the adjuncts are in canonical order.
+    #[must_use]
+    pub fn is_normalized(&self) -> bool {
+        self.adjuncts.iter().map(Bundle::commitment).is_sorted()
+    }
+
+    /// Split the aggregate into its bundle and adjuncts.
+    #[must_use]
+    pub fn into_parts(self) -> (Bundle<ProofStamp>, Vec<Bundle<PointerStamp>>) {
+        (self.bundle, self.adjuncts)
+    }
+
+    /// Net value leaving the pool across the bundle and every adjunct.
+    ///
+    /// Each value balance is in range, but their sum need not be, so it is
+    /// widened.
+    #[must_use]
+    pub fn value_balance(&self) -> i128 {
+        i128::from(self.bundle.value_balance)
+            + self
+                .adjuncts
+                .iter()
+                .map(|adjunct| i128::from(adjunct.value_balance))
+                .sum::<i128>()
+    }
+
+    /// Encoded size of the bundle and every adjunct. See [`Bundle::weight`].
+    #[must_use]
+    pub fn weight(&self) -> usize {
+        self.bundle.weight() + self.adjuncts.iter().map(Bundle::weight).sum::<usize>()
+    }
+
+    /// What each bundle contributes: the aggregate's own bundle at index 0,
+    /// then each adjunct in order.
+    ///
+    /// Everything here is derived from public data, without verifying; an
+    /// indexer would normally summarize only aggregates it has accepted.
+    pub fn summaries(&self) -> impl Iterator<Item = BundleSummary> {
+        iter::once(self.bundle.as_dyn())
+            .chain(self.adjuncts.iter().map(Bundle::as_dyn))
+            .enumerate()
+            .map(|(index, bundle)| BundleSummary {
+                index,
+                commitment: bundle.commitment(),
+                actions: bundle.actions.len(),
+                value_balance: bundle.value_balance,
+                bvk: public::BindingVerificationKey::derive(&bundle.actions, bundle.value_balance),
+            })
+    }
+
+    /// The index, as in [`summaries`](Self::summaries), of the bundle that
+    /// published `tachygram`, where that can be told.
+    ///
+    /// Tachygrams are not linked to actions on the wire: the merged stamp
+    /// publishes one set for every bundle it covers. Only an autonome's
+    /// tachygrams are known to be its own, so for an aggregate with adjuncts
+    /// this is `None`, as it is for a tachygram the stamp does not publish.
+    #[must_use]
+    pub fn tachygram_origin(&self, tachygram: &Tachygram) -> Option<usize> {
+        (self.adjuncts.is_empty() && self.bundle.stamp.tachygrams.contains(tachygram)).then_some(0)
+    }
+
+    /// Check that every adjunct points to `wtxid`, and that the stamp covers
+    /// exactly the actions of the bundle and its adjuncts, each once.
+    ///
+    /// Signatures and the proof are not checked here: see
+    /// [`Bundle::verify_for_block`].
+    pub fn verify(&self, wtxid: &[u8; 64]) -> Result<(), VerificationError> {
+        let adjuncts: Vec<&Bundle<dyn StampState>> =
+            self.adjuncts.iter().map(Bundle::as_dyn).collect();
+
+        self.bundle
+            .verify_pointers(wtxid, &adjuncts)
+            .map_err(VerificationError::Pointers)?;
+        self.bundle
+            .verify_coverage(&adjuncts)
+            .map_err(VerificationError::Coverage)?;
+        Ok(())
+    }
+}
+
+/// One bundle's part in an aggregate, for presenting a transaction on its
+/// own.
+#[derive(Clone, Copy, Debug)]
+pub struct BundleSummary {
+    /// 0 for the aggregate's own bundle, `i + 1` for adjunct `i`.
+    pub index: usize,
+    /// The bundle commitment. See [`Bundle::commitment`].
+    pub commitment: [u8; 32],
+    /// Number of actions.
+    pub actions: usize,
+    /// Net value leaving the pool.
+    pub value_balance: value::Balance,
+    /// The binding verification key the bundle's signature is checked
+    /// against.
+    pub bvk: public::BindingVerificationKey,
+}
+
+/// Autonomes [`Aggregate::merge`] could not merge, returned intact with the
+/// aggregate.
+#[derive(Debug, Display, Error)]
+#[display("merge into aggregate failed: {error}")]
+pub struct MergeRejected {
+    /// The aggregate, unchanged.
+    pub aggregate: Aggregate,
+    /// The autonomes, in the order given, with their own stamps.
+    pub autonomes: Vec<Bundle<ProofStamp>>,
+    /// Why they could not be merged.
+    #[error(source)]
+    pub error: AggregateError,
+}
+
+/// A bundle the [`Aggregator`] could not merge, returned intact.
+#[derive(Debug, Display, Error)]
+#[display("bundle {index} rejected from aggregate: {error}")]
+pub struct Rejected {
+    /// Number of bundles accepted before this one.
+    pub index: usize,
+    /// The bundle, with its own stamp, for inclusion on its own.
+    pub bundle: Box<Bundle<ProofStamp>>,
+    /// Why it could not be merged.
+    #[error(source)]
+    pub error: AggregateError,
+}
+
+/// Builds an aggregate from bundles arriving over time.
+///
+/// The first bundle pushed keeps the running merged stamp; each later one is
+/// merged into it and stripped. A bundle that cannot be merged is returned
+/// intact, so it can still be included in its own transaction. Adjunct
+/// pointers are only known once the aggregate's transaction is final, so they
+/// are assigned by [`seal`](Self::seal).
+///
+/// Bundles that would take the aggregate past its [`Limits`] are turned away
+/// before any proving.
+#[derive(Debug, Default)]
+pub struct Aggregator {
+    limits: Limits,
+    based: Option<Bundle<ProofStamp>>,
+    covered: BTreeSet<action::Descriptor>,
+    stripped: Vec<Stripped>,
+}
+
+impl Aggregator {
+    /// Start an empty aggregator, with no [`Limits`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            limits: Limits::default(),
+            based: None,
+            covered: BTreeSet::new(),
+            stripped: Vec::new(),
+        }
+    }
+
+    /// Hold the aggregate to `limits` instead of the default.
+    pub const fn limits(&mut self, limits: Limits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Number of bundles accepted.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        usize::from(self.based.is_some()) + self.stripped.len()
+    }
+
+    /// Whether no bundle has been accepted.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.based.is_none()
+    }
+
+    /// Number of actions across the accepted bundles.
+    #[must_use]
+    pub fn action_count(&self) -> usize {
+        self.covered.len()
+    }
+
+    /// Number of tachygrams on the running stamp.
+    #[must_use]
+    pub fn tachygram_count(&self) -> usize {
+        self.based
+            .as_ref()
+            .map_or(0, |based| based.stamp.tachygrams.len())
+    }
+
+    /// Number of merges folded into the running stamp, as
+    /// [`Limits::max_depth`] counts them.
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.stripped.len()
+    }
+
+    /// Anchor of the running stamp, or `None` if no bundle was accepted.
+    ///
+    /// A stamp records only the anchor it was proven or lifted to, not those
+    /// of the stamps merged into it, so this is the one anchor there is.
+    #[must_use]
+    pub fn anchor(&self) -> Option<Anchor> {
+        self.based.as_ref().map(|based| based.stamp.anchor)
+    }
+
+    /// Merge a bundle into the running stamp.
+    ///
+    /// Merging proves, so this is the expensive step. The running stamp is
+    /// left as it was if the merge fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the bundle intact if it is not an autonome, if it would exceed
+    /// the limits, or if its stamp does not merge, as when it shares an action
+    /// or a tachygram with a bundle already accepted.
+    pub fn push<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+        bundle: Bundle<ProofStamp>,
+    ) -> Result<(), Rejected> {
+        let index = self.len();
+        if let Some(error) = self.screen(index, &bundle) {
+            return Err(Rejected {
+                index,
+                bundle: Box::new(bundle),
+                error,
+            });
+        }
+        let descriptors: BTreeSet<action::Descriptor> = bundle.descriptors().into_iter().collect();
+
+        let Some(based) = self.based.as_mut() else {
+            self.covered = descriptors;
+            self.based = Some(bundle);
+            return Ok(());
+        };
+
+        match fold(rng, based.stamp.clone(), &self.covered, &bundle, &[]) {
+            Ok(merged) => {
+                based.stamp = merged;
+                self.covered.extend(descriptors);
+                self.stripped.push(Stripped::split(bundle).0);
+                Ok(())
+            },
+            Err(error) => Err(Rejected {
+                index,
+                bundle: Box::new(bundle),
+                error,
+            }),
+        }
+    }
+
+    /// Encoded size of the aggregate [`seal`](Self::seal) would produce: the
+    /// bundle with the running stamp, and each adjunct with its pointer.
+    #[must_use]
+    pub fn estimated_serialized_size(&self) -> usize {
+        self.based.as_ref().map_or(0, Bundle::weight)
+            + self
+                .stripped
+                .iter()
+                .map(|parts| bundle::body_len(parts.actions.len()) + 64)
+                .sum::<usize>()
+    }
+
+    /// The cheap reasons to turn a bundle away, checked before merging.
+    fn screen(&self, index: usize, bundle: &Bundle<ProofStamp>) -> Option<AggregateError> {
+        if !bundle.is_autonome() {
+            return Some(AggregateError::NotAutonome(index));
+        }
+        if self.action_count() + bundle.actions.len() > self.limits.max_actions {
+            return Some(AggregateError::TooManyActions);
+        }
+        if self.tachygram_count() + bundle.stamp.tachygrams.len() > self.limits.max_tachygrams {
+            return Some(AggregateError::TooManyTachygrams);
+        }
+        if self.based.is_some() && self.depth() >= self.limits.max_depth {
+            return Some(AggregateError::TooDeep);
+        }
+        None
+    }
+
+    /// Finish the aggregate, or `None` if no bundle was accepted.
+    ///
+    /// `compute_wtxid` maps the bundle carrying the merged stamp to the wtxid
+    /// of its transaction, and the adjuncts are pointed there.
+    #[must_use]
+    pub fn seal(
+        self,
+        compute_wtxid: impl FnOnce(&Bundle<ProofStamp>) -> PointerStamp,
+    ) -> Option<Aggregate> {
+        let bundle = self.based?;
+        let wtxid = compute_wtxid(&bundle);
+        let adjuncts = self
+            .stripped
+            .into_iter()
+            .map(|parts| parts.point(wtxid))
+            .collect();
+        let mut aggregate = Aggregate { bundle, adjuncts };
+        aggregate.normalize();
+        Some(aggregate)
+    }
+}
+
+/// Merge the stamp of `autonome` into `stamp`, which covers `covered`,
+/// lifting whichever is at the earlier anchor over the matching segment in
+/// `lifts`.
+///
+/// The merge consumes both stamps, so the autonome's is copied: on failure
+/// the caller keeps the autonome, and whatever `stamp` was copied from.
+fn fold<RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+    stamp: ProofStamp,
+    covered: &BTreeSet<action::Descriptor>,
+    autonome: &Bundle<ProofStamp>,
+    lifts: &[ragu::Pcd<pool::AnchorChain>],
+) -> Result<ProofStamp, AggregateError> {
+    if let Some(&tachygram) = stamp.collisions(&autonome.stamp).first() {
+        return Err(AggregateError::TachygramCollision(tachygram));
+    }
+    let descriptors: BTreeSet<action::Descriptor> = autonome.descriptors().into_iter().collect();
+    ProofStamp::merge_lifting(
+        rng,
+        (stamp, covered),
+        (autonome.stamp.clone(), &descriptors),
+        lifts,
+    )
+    .map_err(AggregateError::Merge)
+}
+
+/// An autonome whose stamp has been merged away, awaiting the wtxid its
+/// pointer will carry.
+#[derive(Clone, Debug)]
+struct Stripped {
+    value_balance: value::Balance,
+    actions: Vec<Action>,
+    binding_sig: bundle::Signature,
+}
+
+impl Stripped {
+    fn split(bundle: Bundle<ProofStamp>) -> (Self, ProofStamp) {
+        let Bundle {
+            value_balance,
+            actions,
+            binding_sig,
+            stamp,
+        } = bundle;
+        (
+            Self {
+                value_balance,
+                actions,
+                binding_sig,
+            },
+            stamp,
+        )
+    }
+
+    fn point(self, wtxid: PointerStamp) -> Bundle<PointerStamp> {
+        Bundle {
+            value_balance: self.value_balance,
+            actions: self.actions,
+            binding_sig: self.binding_sig,
+            stamp: wtxid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+    use crate::{
+        bundle,
+        fixtures::{
+            PoolSim, WalletSim, build_anchor_chain_pcd, build_output_bundle, mock_wtxid,
+            random_block, shared_sk,
+        },
+    };
+
+    /// Merging strips autonomes to adjuncts, in canonical order, and a later
+    /// merge re-points the earlier adjuncts to the new wtxid.
+    #[test]
+    fn merge_strips_and_repoints_adjuncts() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let anchor = PoolSim::genesis(rng).anchor();
+        let based = build_output_bundle(rng, &wallet, anchor, 400);
+        let first = build_output_bundle(rng, &wallet, anchor, 300);
+        let second = build_output_bundle(rng, &wallet, anchor, 200);
+
+        let aggregate = Aggregate::new(based, vec![])
+            .merge(rng, [first], mock_wtxid)
+            .expect("merge first autonome");
+        let first_wtxid: [u8; 64] = mock_wtxid(aggregate.bundle()).into();
+        assert_eq!(aggregate.adjuncts().len(), 1);
+        aggregate
+            .verify(&first_wtxid)
+            .expect("aggregate covers its adjunct");
+        assert_eq!(aggregate.value_balance(), -700);
+
+        let aggregate = aggregate
+            .merge(rng, [second], mock_wtxid)
+            .expect("merge second autonome");
+        let wtxid: [u8; 64] = mock_wtxid(aggregate.bundle()).into();
+        aggregate
+            .verify(&wtxid)
+            .expect("aggregate covers both adjuncts");
+        assert_eq!(aggregate.value_balance(), -900);
+
+        assert!(aggregate.is_normalized());
+        let (bundle, adjuncts) = aggregate.clone().into_parts();
+        let mut reordered = Aggregate::new(bundle, adjuncts.into_iter().rev().collect());
+        assert!(!reordered.is_normalized());
+        reordered.normalize();
+        assert_eq!(reordered.adjuncts(), aggregate.adjuncts());
+
+        let err = aggregate
+            .verify(&first_wtxid)
+            .expect_err("adjuncts point to the new wtxid");
+        assert!(matches!(
+            err,
+            VerificationError::Pointers(bundle::VerifyPointersError::AdjunctPointerMismatch)
+        ));
+
+        let adjuncts: Vec<&Bundle<PointerStamp>> = aggregate.adjuncts().iter().collect();
+        aggregate
+            .bundle()
+            .verify(rng, &wtxid, &adjuncts)
+            .expect("merged proof verifies");
+
+        let (bundle, _adjuncts) = aggregate.into_parts();
+        let lone = build_output_bundle(rng, &wallet, anchor, 100);
+        let autonome = build_output_bundle(rng, &wallet, anchor, 50);
+        let rejected = Aggregate::new(lone.clone(), vec![])
+            .merge(rng, [autonome.clone(), bundle.clone()], mock_wtxid)
+            .expect_err("an aggregate cannot be merged as an autonome");
+        assert!(matches!(rejected.error, AggregateError::NotAutonome(2)));
+        assert_eq!(*rejected.aggregate.bundle(), lone);
+        assert!(rejected.aggregate.adjuncts().is_empty());
+        assert_eq!(rejected.autonomes, vec![autonome, bundle]);
+    }
+
+    /// Bundles that fail to merge come back intact and leave the running
+    /// stamp untouched; the sealed aggregate covers the rest.
+    #[test]
+    fn aggregator_returns_unmergeable_bundles() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let anchor = PoolSim::genesis(rng).anchor();
+        let first = build_output_bundle(rng, &wallet, anchor, 300);
+        let second = build_output_bundle(rng, &wallet, anchor, 200);
+
+        assert!(Aggregator::new().seal(mock_wtxid).is_none());
+
+        let mut aggregator = Aggregator::new();
+        aggregator
+            .push(rng, first)
+            .expect("first bundle is accepted");
+        aggregator
+            .push(rng, second.clone())
+            .expect("second bundle merges");
+
+        let rejected = aggregator
+            .push(rng, second.clone())
+            .expect_err("a repeated bundle cannot merge");
+        assert_eq!(rejected.index, 2);
+        assert!(matches!(
+            rejected.error,
+            AggregateError::TachygramCollision(_)
+        ));
+        assert_eq!(*rejected.bundle, second);
+        assert_eq!(aggregator.len(), 2);
+
+        let aggregate = aggregator.seal(mock_wtxid).expect("bundles were accepted");
+        let wtxid: [u8; 64] = mock_wtxid(aggregate.bundle()).into();
+        aggregate
+            .verify(&wtxid)
+            .expect("aggregate covers its adjunct");
+        let adjuncts: Vec<&Bundle<PointerStamp>> = aggregate.adjuncts().iter().collect();
+        aggregate
+            .bundle()
+            .verify(rng, &wtxid, &adjuncts)
+            .expect("running stamp survives the failed merge");
+
+        let (bundle, _adjuncts) = aggregate.into_parts();
+        let mut aggregator = Aggregator::new();
+        let rejected = aggregator
+            .push(rng, bundle)
+            .expect_err("an aggregate is not an autonome");
+        assert!(matches!(rejected.error, AggregateError::NotAutonome(0)));
+        assert!(aggregator.is_empty());
+    }
+
+    /// Bundles past the limits are turned away before merging, and the
+    /// aggregate weighs what its parts do.
+    #[test]
+    fn aggregator_enforces_limits() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let anchor = PoolSim::genesis(rng).anchor();
+        let first = build_output_bundle(rng, &wallet, anchor, 300);
+        let second = build_output_bundle(rng, &wallet, anchor, 200);
+        let third = build_output_bundle(rng, &wallet, anchor, 100);
+
+        let mut reject = |limits: Limits| {
+            let mut aggregator = Aggregator::new();
+            aggregator.limits(limits);
+            aggregator
+                .push(rng, first.clone())
+                .expect("first bundle is within limits");
+            aggregator
+                .push(rng, second.clone())
+                .expect_err("second bundle exceeds limits")
+                .error
+        };
+        assert!(matches!(
+            reject(Limits {
+                max_depth: 0,
+                ..Limits::default()
+            }),
+            AggregateError::TooDeep
+        ));
+        assert!(matches!(
+            reject(Limits {
+                max_actions: 1,
+                ..Limits::default()
+            }),
+            AggregateError::TooManyActions
+        ));
+        assert!(matches!(
+            reject(Limits {
+                max_tachygrams: first.stamp.tachygrams.len(),
+                ..Limits::default()
+            }),
+            AggregateError::TooManyTachygrams
+        ));
+
+        let mut aggregator = Aggregator::new();
+        aggregator.limits(Limits {
+            max_actions: 2,
+            ..Limits::default()
+        });
+        aggregator.push(rng, first).expect("within limits");
+        aggregator.push(rng, second).expect("within limits");
+        assert_eq!(aggregator.action_count(), 2);
+        assert_eq!(aggregator.tachygram_count(), 2);
+        assert_eq!(aggregator.depth(), 1);
+        assert_eq!(aggregator.anchor(), Some(anchor));
+        let rejected = aggregator
+            .push(rng, third)
+            .expect_err("third bundle exceeds the action limit");
+        assert!(matches!(rejected.error, AggregateError::TooManyActions));
+
+        let estimate = aggregator.estimated_serialized_size();
+        let aggregate = aggregator.seal(mock_wtxid).expect("bundles were accepted");
+        assert_eq!(aggregate.weight(), estimate, "sealing adds no bytes");
+        let mut buf = Vec::new();
+        aggregate.bundle().write(&mut buf).expect("write");
+        for adjunct in aggregate.adjuncts() {
+            adjunct.write(&mut buf).expect("write");
+        }
+        assert_eq!(aggregate.weight(), buf.len(), "weight is the encoded size");
+    }
+
+    /// Each push keeps the later anchor: a newer autonome lifts the aggregate,
+    /// an older one is lifted to it, and one with no joining segment is
+    /// returned without touching the aggregate.
+    #[test]
+    fn push_keeps_the_later_anchor() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let mut pool = PoolSim::genesis(rng);
+        pool.mine(random_block(rng, 1, 2));
+        let old_height = pool.height();
+        let old_anchor = pool.anchor_at(old_height);
+        pool.advance(2, |_| random_block(rng, 1, 2));
+        let new_height = pool.height();
+        let new_anchor = pool.anchor_at(new_height);
+        assert_eq!(old_height.epoch(), new_height.epoch());
+        let chain = build_anchor_chain_pcd(rng, &pool, old_height.next().unwrap()..=new_height);
+
+        let mut aggregate =
+            Aggregate::new(build_output_bundle(rng, &wallet, old_anchor, 400), vec![]);
+
+        aggregate
+            .push(
+                rng,
+                build_output_bundle(rng, &wallet, new_anchor, 300),
+                &[chain.clone()],
+                mock_wtxid,
+            )
+            .expect("a newer autonome lifts the aggregate");
+        assert_eq!(aggregate.bundle().stamp.anchor, new_anchor);
+
+        aggregate
+            .push(
+                rng,
+                build_output_bundle(rng, &wallet, old_anchor, 200),
+                &[chain],
+                mock_wtxid,
+            )
+            .expect("an older autonome is lifted to the aggregate");
+        assert_eq!(aggregate.bundle().stamp.anchor, new_anchor);
+
+        let stale = build_output_bundle(rng, &wallet, old_anchor, 100);
+        let rejected = aggregate
+            .push(rng, stale.clone(), &[], mock_wtxid)
+            .expect_err("no segment joins the anchors");
+        assert!(matches!(
+            rejected.error,
+            AggregateError::Merge(ProveError::AnchorUnreachable)
+        ));
+        assert_eq!(*rejected.bundle, stale);
+        assert_eq!(aggregate.adjuncts().len(), 2);
+
+        let wtxid: [u8; 64] = mock_wtxid(aggregate.bundle()).into();
+        aggregate
+            .verify(&wtxid)
+            .expect("aggregate covers its adjuncts");
+        let adjuncts: Vec<&Bundle<PointerStamp>> = aggregate.adjuncts().iter().collect();
+        aggregate
+            .bundle()
+            .verify(rng, &wtxid, &adjuncts)
+            .expect("folded proof verifies");
+    }
+
+    /// Shared tachygrams are found natively, before a merge is proven.
+    #[test]
+    fn collisions_found_before_proving() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let anchor = PoolSim::genesis(rng).anchor();
+        let first = build_output_bundle(rng, &wallet, anchor, 300);
+        let second = build_output_bundle(rng, &wallet, anchor, 200);
+
+        let rejected = Aggregate::new(first.clone(), vec![])
+            .merge(rng, [second, first], mock_wtxid)
+            .expect_err("a repeated autonome cannot merge");
+        let AggregateError::TachygramCollision(tachygram) = rejected.error else {
+            panic!("expected TachygramCollision, got {:?}", rejected.error);
+        };
+        assert!(
+            rejected.autonomes[1].stamp.tachygrams.contains(&tachygram),
+            "the repeated stamp publishes the shared tachygram"
+        );
+    }
+
+    /// Summaries follow the aggregate's bundles, and a tachygram is traced to
+    /// its bundle only while the stamp covers one.
+    #[test]
+    fn summaries_decompose_the_aggregate() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let anchor = PoolSim::genesis(rng).anchor();
+        let based = build_output_bundle(rng, &wallet, anchor, 400);
+        let other = build_output_bundle(rng, &wallet, anchor, 300);
+        let tachygram = *based.stamp.tachygrams.first().expect("output tachygram");
+
+        let autonome = Aggregate::new(based, vec![]);
+        assert_eq!(autonome.tachygram_origin(&tachygram), Some(0));
+        assert_eq!(
+            autonome.tachygram_origin(other.stamp.tachygrams.first().expect("output tachygram")),
+            None
+        );
+
+        let aggregate = autonome
+            .merge(rng, [other], mock_wtxid)
+            .expect("merge autonome");
+        assert_eq!(aggregate.tachygram_origin(&tachygram), None);
+
+        let summaries: Vec<BundleSummary> = aggregate.summaries().collect();
+        assert_eq!(summaries.len(), 2);
+        let bundles = iter::once(aggregate.bundle().as_dyn())
+            .chain(aggregate.adjuncts().iter().map(Bundle::as_dyn));
+        for (index, (summary, bundle)) in summaries.iter().zip(bundles).enumerate() {
+            assert_eq!(summary.index, index);
+            assert_eq!(summary.commitment, bundle.commitment());
+            assert_eq!(summary.actions, bundle.actions.len());
+            assert_eq!(
+                summary.bvk,
+                public::BindingVerificationKey::derive(&bundle.actions, bundle.value_balance)
+            );
+        }
+        let total: i128 = summaries
+            .iter()
+            .map(|summary| i128::from(summary.value_balance))
+            .sum();
+        assert_eq!(total, aggregate.value_balance());
+    }
+}
@@ -0,0 +1,286 @@
+//! The Tachyon data of a block.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
+
+use crate::{
+    aggregate::Aggregate,
+    primitives::{Anchor, EpochIndex, Tachygram, TachygramSetCommit, TachygramSetPoly},
+    stamp,
+};
+
+/// Errors from checking a block's Tachyon data.
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "intentional name")]
+pub enum BlockError {
+    /// A tachygram is published by more than one stamp in the block.
+    #[display("duplicate tachygram {_0:?}")]
+    DuplicateTachygram(#[error(not(source))] Tachygram),
+    /// The block's anchor is not the one its stamps advance to.
+    #[display("anchor does not follow from the block's stamps")]
+    AnchorMismatch,
+    /// A tachygram was already published by a recent block.
+    #[display("tachygram {_0:?} published in a recent epoch")]
+    RepeatedTachygram(#[error(not(source))] Tachygram),
+}
+
+/// The aggregates a block includes, and the anchor transition they make.
+///
+/// `prev_anchor` is the anchor the block starts from: the previous block's
+/// anchor, lifted with [`Anchor::next_epoch`] if the block opens an epoch.
+/// Each aggregate's stamp is absorbed in order, so `anchor` is the anchor
+/// after the block's last stamp, or after one empty step if it has none.
+#[derive(Clone, Debug)]
+pub struct TachyonBlockData {
+    /// The anchor before this block's stamps.
+    pub prev_anchor: Anchor,
+
+    /// The anchor after this block's stamps.
+    pub anchor: Anchor,
+
+    /// Aggregates, each with the one stamp covering it, in block order.
+    pub aggregates: Vec<Aggregate>,
+}
+
+impl TachyonBlockData {
+    /// The anchor the aggregates advance `prev_anchor` to.
+    ///
+    /// Each stamp's set commitment is independent of the others. With the
+    /// `rayon` feature they are computed in parallel, and they are absorbed
+    /// with [`Anchor::next_stamps`].
+    #[must_use]
+    pub fn next_anchor(&self) -> Anchor {
+        if self.aggregates.is_empty() {
+            return self.prev_anchor.next_empty();
+        }
+
+        #[cfg(feature = "rayon")]
+        let iter = self.aggregates.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let iter = self.aggregates.iter();
+
+        let commits: Vec<TachygramSetCommit> = iter
+            .map(|aggregate| {
+                let tachygrams = &aggregate.bundle().stamp.tachygrams;
+                tachygrams
+                    .iter()
+                    .copied()
+                    .collect::<TachygramSetPoly>()
+                    .commit()
+            })
+            .collect();
+        self.prev_anchor.next_stamps(&commits)
+    }
+
+    /// Check that no tachygram repeats within the block, and that `anchor`
+    /// follows from `prev_anchor` and the included stamps.
+    ///
+    /// The aggregates themselves are not verified here: see
+    /// [`Aggregate::verify`] and
+    /// [`Bundle::verify_for_block`](crate::Bundle::verify_for_block). Neither
+    /// are tachygrams published in earlier blocks.
+    pub fn check(&self) -> Result<(), BlockError> {
+        let duplicates = stamp::duplicate_tachygrams(
+            self.aggregates
+                .iter()
+                .map(|aggregate| &aggregate.bundle().stamp),
+        );
+        if let Some(&tachygram) = duplicates.first() {
+            return Err(BlockError::DuplicateTachygram(tachygram));
+        }
+
+        if self.next_anchor() != self.anchor {
+            return Err(BlockError::AnchorMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// The tachygrams published in recent epochs, as a validator tracks them.
+///
+/// Consensus rejects a tachygram repeated within the current epoch or the
+/// `window` epochs before it, the same span [`AnchorPolicy`] accepts anchors
+/// from. A stamp cannot be anchored any earlier, so tachygrams from older
+/// epochs are dropped from the working set. The anchor chain still commits to
+/// them, so the chain stays verifiable.
+///
+/// [`AnchorPolicy`]: crate::primitives::AnchorPolicy
+#[derive(Clone, Debug)]
+pub struct RecentTachygrams {
+    window: u32,
+    epochs: BTreeMap<EpochIndex, BTreeSet<Tachygram>>,
+}
+
+impl RecentTachygrams {
+    /// Track tachygrams over the current epoch and `window` before it.
+    #[must_use]
+    pub const fn new(window: u32) -> Self {
+        Self {
+            window,
+            epochs: BTreeMap::new(),
+        }
+    }
+
+    /// Whether a tracked epoch published `tachygram`.
+    #[must_use]
+    pub fn contains(&self, tachygram: &Tachygram) -> bool {
+        self.epochs.values().any(|set| set.contains(tachygram))
+    }
+
+    /// Number of tachygrams tracked.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.epochs.values().map(BTreeSet::len).sum()
+    }
+
+    /// Whether no tachygram is tracked.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.epochs.values().all(BTreeSet::is_empty)
+    }
+
+    /// Check a block landing in `epoch` and record its tachygrams.
+    ///
+    /// The block is first checked as by [`TachyonBlockData::check`]. Epochs
+    /// that fall out of the window as of `epoch` are then pruned.
+    ///
+    /// # Errors
+    ///
+    /// As [`TachyonBlockData::check`], or
+    /// [`BlockError::RepeatedTachygram`] if the block repeats a tracked
+    /// tachygram. On error nothing is recorded or pruned.
+    pub fn apply(&mut self, epoch: EpochIndex, block: &TachyonBlockData) -> Result<(), BlockError> {
+        block.check()?;
+        let tachygrams = block
+            .aggregates
+            .iter()
+            .flat_map(|aggregate| aggregate.bundle().stamp.tachygrams.iter().copied());
+        if let Some(tachygram) = tachygrams
+            .clone()
+            .find(|tachygram| self.contains(tachygram))
+        {
+            return Err(BlockError::RepeatedTachygram(tachygram));
+        }
+        self.epochs.entry(epoch).or_default().extend(tachygrams);
+        self.prune(epoch);
+        Ok(())
+    }
+
+    /// Drop the tachygrams of epochs more than the window before `current`.
+    pub fn prune(&mut self, current: EpochIndex) {
+        let oldest = EpochIndex(current.0.saturating_sub(self.window));
+        self.epochs = self.epochs.split_off(&oldest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+    use crate::fixtures::{PoolSim, WalletSim, build_output_bundle, mock_wtxid, shared_sk};
+
+    /// A block's anchor folds its stamps in order, and a stamp included twice
+    /// repeats its tachygrams.
+    #[test]
+    fn block_checks_tachygrams_and_anchor() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let prev_anchor = PoolSim::genesis(rng).anchor();
+
+        let merged = Aggregate::new(build_output_bundle(rng, &wallet, prev_anchor, 300), vec![])
+            .merge(
+                rng,
+                [build_output_bundle(rng, &wallet, prev_anchor, 200)],
+                mock_wtxid,
+            )
+            .expect("merge autonome");
+        let autonome = Aggregate::new(build_output_bundle(rng, &wallet, prev_anchor, 100), vec![]);
+
+        let commit = |aggregate: &Aggregate| {
+            aggregate
+                .bundle()
+                .stamp
+                .tachygrams
+                .iter()
+                .copied()
+                .collect::<TachygramSetPoly>()
+                .commit()
+        };
+        let mut block = TachyonBlockData {
+            prev_anchor,
+            anchor: prev_anchor
+                .next_stamp(&commit(&merged))
+                .next_stamp(&commit(&autonome)),
+            aggregates: vec![merged, autonome],
+        };
+        assert_eq!(block.check(), Ok(()));
+
+        block.aggregates.reverse();
+        assert_eq!(block.check(), Err(BlockError::AnchorMismatch));
+
+        let repeated = block.aggregates[0].clone();
+        block.aggregates.push(repeated);
+        block.anchor = block.next_anchor();
+        assert!(matches!(
+            block.check(),
+            Err(BlockError::DuplicateTachygram(_))
+        ));
+
+        let empty = TachyonBlockData {
+            prev_anchor,
+            anchor: prev_anchor.next_empty(),
+            aggregates: vec![],
+        };
+        assert_eq!(empty.check(), Ok(()));
+    }
+
+    /// A tachygram is rejected while its epoch is in the window, and
+    /// accepted again once the epoch is pruned.
+    #[test]
+    fn recent_tachygrams_prune_past_the_window() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let wallet = WalletSim::new(shared_sk());
+        let prev_anchor = PoolSim::genesis(rng).anchor();
+        let aggregate = Aggregate::new(build_output_bundle(rng, &wallet, prev_anchor, 300), vec![]);
+        let mut block = TachyonBlockData {
+            prev_anchor,
+            anchor: prev_anchor,
+            aggregates: vec![aggregate],
+        };
+        block.anchor = block.next_anchor();
+        let tachygram = *block.aggregates[0]
+            .bundle()
+            .stamp
+            .tachygrams
+            .first()
+            .expect("output publishes one");
+
+        let mut recent = RecentTachygrams::new(1);
+        assert!(recent.is_empty());
+        recent.apply(EpochIndex(0), &block).expect("first block");
+        assert!(recent.contains(&tachygram));
+        assert_eq!(recent.len(), 1);
+
+        assert_eq!(
+            recent.apply(EpochIndex(1), &block),
+            Err(BlockError::RepeatedTachygram(tachygram))
+        );
+        assert!(recent.contains(&tachygram), "rejection records nothing");
+
+        recent.prune(EpochIndex(2));
+        assert!(!recent.contains(&tachygram), "epoch 0 left the window");
+        recent
+            .apply(EpochIndex(2), &block)
+            .expect("pruned tachygram");
+    }
+}
@@ -0,0 +1,220 @@
+//! High-level bundle construction.
+
+use alloc::vec::Vec;
+
+use derive_more::{Debug, Display, Error};
+use rand_core::{CryptoRng, RngCore};
+
+use super::{Bundle, Plan, PlanError, Policy, PolicyError};
+use crate::{
+    action,
+    entropy::ActionEntropy,
+    keys::{PaymentKey, ProofAuthorizingKey, private},
+    note::{self, Note},
+    primitives::Anchor,
+    stamp::{
+        ProofStamp, ProveError,
+        proof::{delegation, spendable},
+    },
+    value,
+};
+
+/// Errors that can occur while building a bundle.
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The spends do not cover the outputs, the fee, and a zatoshi for each
+    /// change note the padding splits the remainder into.
+    #[display("insufficient funds for outputs, fee, and padding")]
+    InsufficientFunds,
+    /// A value falls outside the representable range.
+    #[display("value out of range")]
+    ValueOutOfRange,
+    /// Value is left over after the outputs and fee, but the policy has no
+    /// room for a change output to return it.
+    #[display("no room to return change")]
+    NoRoomForChange,
+    /// Signing the bundle plan failed.
+    #[display("signing failed: {_0}")]
+    Sign(PlanError),
+    /// Proving the stamp failed.
+    #[display("proving failed: {_0}")]
+    Prove(ProveError),
+    /// No bundle shape satisfies the policy.
+    #[display("bundle shape policy violated: {_0}")]
+    Policy(PolicyError),
+}
+
+/// A spend queued on a [`Builder`]: the note, its action entropy, and the
+/// PCDs [`stamp::Plan::prove`](crate::stamp::Plan::prove) consumes.
+type QueuedSpend = (
+    Note,
+    ActionEntropy,
+    ragu::Pcd<delegation::NullifierHeader>,
+    ragu::Pcd<spendable::SpendableHeader>,
+);
+
+/// Builds a signed, proof-stamped bundle from notes and payments.
+///
+/// Wraps the [`Plan`] choreography: outputs are turned into notes, any
+/// remaining value returns to a change key, the actions are padded with
+/// [`action::GroupBuilder`] to the shape the [`Policy`] requires, and the plan
+/// is signed and proven in one call to [`build`](Self::build).
+#[derive(Debug)]
+pub struct Builder {
+    change: PaymentKey,
+    fee: u64,
+    policy: Policy,
+    #[debug(skip)]
+    spends: Vec<QueuedSpend>,
+    outputs: Vec<(PaymentKey, value::Positive)>,
+}
+
+impl Builder {
+    /// Start a bundle paying `fee`, returning change to `change`, shaped by
+    /// the default [`Policy`].
+    #[must_use]
+    pub fn new(change: PaymentKey, fee: u64) -> Self {
+        Self {
+            change,
+            fee,
+            policy: Policy::default(),
+            spends: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Shape the bundle by `policy` instead of the default.
+    pub const fn policy(&mut self, policy: Policy) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Spend a note, proving it spendable with the given nullifier range and
+    /// spendable lineage.
+    pub fn add_spend(
+        &mut self,
+        note: Note,
+        theta: ActionEntropy,
+        nf_pcd: ragu::Pcd<delegation::NullifierHeader>,
+        spendable_pcd: ragu::Pcd<spendable::SpendableHeader>,
+    ) -> &mut Self {
+        self.spends.push((note, theta, nf_pcd, spendable_pcd));
+        self
+    }
+
+    /// Pay `amount` to `pk`.
+    pub fn add_output(&mut self, pk: PaymentKey, amount: value::Positive) -> &mut Self {
+        self.outputs.push((pk, amount));
+        self
+    }
+
+    /// Sign and prove the bundle.
+    ///
+    /// Whatever remains after the outputs and fee returns to the change key,
+    /// split into as many notes as the padding needs; it is never paid as
+    /// extra fee.
+    ///
+    /// `compute_sighash` maps the bundle commitment to the transaction sighash.
+    /// Returns the bundle with the created output notes, change last, so
+    /// their trapdoors can be sent to the recipients.
+    ///
+    /// # Errors
+    ///
+    /// [`BuildError::Policy`] if no shape fits the actions,
+    /// [`BuildError::InsufficientFunds`] if the spends fall short of the
+    /// outputs, fee, and a zatoshi for each change note, and
+    /// [`BuildError::NoRoomForChange`] if a remainder cannot be returned.
+    pub fn build<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        ask: &private::SpendAuthorizingKey,
+        pak: &ProofAuthorizingKey,
+        anchor: Anchor,
+        compute_sighash: impl FnOnce([u8; 32]) -> [u8; 32],
+    ) -> Result<(Bundle<ProofStamp>, Vec<Note>), BuildError> {
+        let Self {
+            change,
+            fee,
+            policy,
+            spends,
+            outputs,
+        } = self;
+
+        let spent: i128 = spends
+            .iter()
+            .map(|&(note, ..)| i128::from(note.value))
+            .sum();
+        let paid: i128 = outputs.iter().map(|&(_, amount)| i128::from(amount)).sum();
+        let available = spent - paid - i128::from(fee);
+        let real = spends.len() + outputs.len();
+
+        let bare = policy.padded_len(real).map_err(BuildError::Policy)?;
+        if available < 0 {
+            return Err(BuildError::InsufficientFunds);
+        }
+        let (change_note, target) = if available == 0 {
+            (None, bare)
+        } else {
+            let target = policy
+                .padded_len(real + 1)
+                .map_err(|_err| BuildError::NoRoomForChange)?;
+            let note = Note {
+                pk: change,
+                value: value::Positive::try_from(available)
+                    .map_err(|_err| BuildError::ValueOutOfRange)?,
+                psi: note::NullifierTrapdoor::random(rng),
+                rcm: note::CommitmentTrapdoor::random(rng),
+            };
+            (Some(note), target)
+        };
+
+        let mut group = action::GroupBuilder::new();
+        let mut spendbind_inputs = Vec::with_capacity(spends.len());
+        for (note, theta, nf_pcd, spendable_pcd) in spends {
+            let rcv = value::Trapdoor::random(rng);
+            group.add_spend(action::Plan::spend(note, theta, rcv, |alpha| {
+                ask.derive_action_private(&alpha).derive_action_public()
+            }));
+            spendbind_inputs.push((nf_pcd, spendable_pcd));
+        }
+
+        let mut notes = Vec::with_capacity(target);
+        for (pk, amount) in outputs {
+            let note = Note {
+                pk,
+                value: amount,
+                psi: note::NullifierTrapdoor::random(rng),
+                rcm: note::CommitmentTrapdoor::random(rng),
+            };
+            group.add_output(action::Plan::output(
+                note,
+                ActionEntropy::random(rng),
+                value::Trapdoor::random(rng),
+            ));
+            notes.push(note);
+        }
+
+        if let Some(note) = change_note {
+            group.add_change(note);
+        }
+        let group = group
+            .build_to(rng, target)
+            .map_err(|_err| BuildError::InsufficientFunds)?;
+        notes.extend(group.change().iter().map(|plan| plan.note));
+
+        let plan = Plan::from(group);
+        let commitment = plan
+            .commitment()
+            .map_err(|_err| BuildError::ValueOutOfRange)?;
+        let sighash = compute_sighash(commitment);
+
+        let unproven = plan.sign(rng, &sighash, ask).map_err(BuildError::Sign)?;
+        let stamp = plan
+            .stamp_plan(anchor)
+            .prove(rng, pak, spendbind_inputs)
+            .map_err(BuildError::Prove)?;
+
+        Ok((unproven.stamp(stamp), notes))
+    }
+}
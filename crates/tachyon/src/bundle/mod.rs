@@ -3,9 +3,14 @@
 //! A bundle is parameterized by bundle state `S: BundleState`.
 //! Actions are constant through state transitions; only the stamp changes.
 //!
+//! - [`Plan`] — unauthorized; [`Plan::sign`] attaches every signature at once
+//! - [`Partial`] — a plan with the signatures collected so far, for signing
+//!   across devices
+//! - `Bundle<Unproven>` — signed but unproven, with no wire representation
 //! - `Bundle<ProofStamp>` — self-contained bundle with a proof stamp
 //! - `Bundle<PointerStamp>` — proof stamp replaced by a pointer stamp naming
-//!   the covering aggregate
+//!   the covering aggregate; [`Transition`] lists what to re-check when a
+//!   bundle moves between this state and the last
 //! - [`TachyonBundle`] — enum of the on-wire forms for mixed contexts
 //!
 //! # Consensus wire format
@@ -68,11 +73,17 @@
 //! 64-byte value: the pointer stamp's `wtxid` directly, or
 //! `hStampActionsTachyon || stamp_data_digest` for a proof stamp.
 
+mod builder;
+mod partial;
+mod policy;
+mod transition;
+mod verifier;
+
 use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use core::{cmp::Ordering, ops::Neg as _};
+use core::{cmp::Ordering, iter, ops::Neg as _};
 
 use corez::io::{self, Read, Write};
 use derive_more::{Debug, Display, Eq as TotalEq, Error, From, IsVariant, PartialEq, TryInto};
@@ -81,14 +92,24 @@ use rand_core::{CryptoRng, RngCore};
 use crate::{
     ActionDigest, ActionDigestError,
     action::{self, Action},
+    block::RecentTachygrams,
     digest::blake2b,
     keys::{private, public},
-    primitives::{Anchor, effect},
+    primitives::{Anchor, AnchorPolicy, Tachygram, effect},
     reddsa, serialization,
     stamp::{self, AggregateIdError, PointerStamp, ProofStamp, StampState, Unproven},
     value,
 };
 
+pub use builder::{BuildError, Builder};
+pub use partial::Partial;
+pub use policy::{Policy, PolicyError};
+pub use transition::{Recheck, Transition, TransitionError};
+pub use verifier::{
+    BatchSignatureError, ProofQueue, QueuedProofError, SignatureBatch, StreamingVerifier,
+    VerifiedBundle, Verifier, VerifierError,
+};
+
 /// The `tachyonBundleState` wire byte. See the module-level wire format
 /// documentation for its role.
 #[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
@@ -166,6 +187,22 @@ impl<S: StampState + 'static> PartialEq for Bundle<S> {
 }
 
 impl<S: BundleState + ?Sized> Bundle<S> {
+    /// Transition to another bundle state by mapping the stamp.
+    ///
+    /// Actions, value balance, and binding signature carry over unchanged.
+    #[must_use]
+    pub fn map_stamp<T: BundleState>(self, transform: impl FnOnce(S) -> T) -> Bundle<T>
+    where
+        S: Sized,
+    {
+        Bundle {
+            actions: self.actions,
+            value_balance: self.value_balance,
+            binding_sig: self.binding_sig,
+            stamp: transform(self.stamp),
+        }
+    }
+
     /// Collect the descriptors of all actions in the bundle, preserving wire
     /// order and duplicates.
     ///
@@ -179,9 +216,11 @@ impl<S: BundleState + ?Sized> Bundle<S> {
 
     /// Digest the bundle's effecting data.
     ///
-    /// This contributes to the transaction sighash. The stamp is excluded
-    /// because it is considered authorizing data, and is malleable during
-    /// aggregation.
+    /// This contributes to the transaction sighash and txid. The stamp,
+    /// including its anchor, is excluded because it is considered authorizing
+    /// data, and is malleable during aggregation; it enters
+    /// [`auth_digest`](Self::auth_digest) instead. A transaction without a
+    /// bundle uses the placeholder from [`TachyonBundle::commitment`].
     ///
     /// The digest binds actions in wire order and is therefore sensitive to
     /// their ordering.
@@ -194,21 +233,38 @@ impl<S: BundleState + ?Sized> Bundle<S> {
         )
     }
 
+    /// Check the bundle's shape against a [`Policy`].
+    ///
+    /// Dummies are indistinguishable from other outputs, so only the action
+    /// count is checked, not the padding limit.
+    pub fn check_policy(&self, policy: &Policy) -> Result<(), PolicyError> {
+        policy.check(self.actions.len())
+    }
+
     /// Verify the bundle's binding signature and all action signatures.
     pub fn verify_signatures(&self, sighash: &[u8; 32]) -> Result<(), SignatureError> {
         // 1. Derive bvk from public data
         let bvk = public::BindingVerificationKey::derive(&self.actions, self.value_balance);
 
+        self.verify_signatures_with(sighash, &bvk)
+    }
+
+    /// Verify the signatures against an already-derived `bvk`.
+    fn verify_signatures_with(
+        &self,
+        sighash: &[u8; 32],
+        bvk: &public::BindingVerificationKey,
+    ) -> Result<(), SignatureError> {
         // 2. Verify binding signature
         bvk.verify(sighash, &self.binding_sig)
             .map_err(|_err| SignatureError::Binding(self.binding_sig))?;
 
         // 3. Verify each action signature
-        for action in &self.actions {
+        for (index, action) in self.actions.iter().enumerate() {
             action
                 .rk
                 .verify(sighash, &action.sig)
-                .map_err(|_err| SignatureError::Action(action.sig))?;
+                .map_err(|_err| SignatureError::Action(index, action.sig))?;
         }
 
         Ok(())
@@ -222,17 +278,23 @@ pub enum SignatureError {
     /// The binding signature is invalid.
     #[display("invalid binding signature {_0:?}")]
     Binding(#[error(not(source))] Signature),
-    /// An action signature is invalid.
-    #[display("invalid action signature {_0:?}")]
-    Action(#[error(not(source))] action::Signature),
+    /// The signature on the action at this index, in wire order, is invalid.
+    #[display("invalid signature {_1:?} on action {_0}")]
+    Action(usize, #[error(not(source))] action::Signature),
+    /// A batch of signatures failed together, though each verifies singly.
+    #[display("signature batch failed")]
+    Batch,
 }
 
 /// Error during proof verification.
 #[derive(Debug, Display, Error)]
 pub enum VerifyProofError {
-    /// An action's cv or rk is the identity point.
-    #[display("action digest error: {_0}")]
-    ActionDigest(ActionDigestError),
+    /// The cv or rk of the action at this index is the identity point.
+    ///
+    /// Actions are indexed in wire order, this bundle's first, then each
+    /// adjunct's in turn.
+    #[display("action digest error on action {_0}: {_1}")]
+    ActionDigest(usize, #[error(source)] ActionDigestError),
     /// The proof system returned an error.
     #[display("proof system error: {_0}")]
     ProofSystem(ragu::Error),
@@ -262,9 +324,22 @@ pub enum VerifyPointersError {
 
 /// Errors during bundle verification.
 #[derive(Debug, Display, Error)]
+#[non_exhaustive]
 pub enum VerificationError {
-    /// The pointer of an adjunct is not the expected aggregate id.
-    #[display("stamp on an adjunct does not match the expected aggregate id")]
+    /// A signature on this bundle is invalid.
+    #[display("signature verification error: {_0}")]
+    Signature(SignatureError),
+    /// A signature on the adjunct at this index is invalid.
+    #[display("signature verification error on adjunct {_0}: {_1}")]
+    AdjunctSignature(usize, #[error(source)] SignatureError),
+    /// The stamp's anchor is outside the range accepted for the landing block.
+    #[display("stamp anchor out of range")]
+    AnchorOutOfRange,
+    /// The stamp publishes a tachygram already published in a recent epoch.
+    #[display("tachygram {_0:?} published in a recent epoch")]
+    RepeatedTachygram(#[error(not(source))] Tachygram),
+    /// An adjunct does not point to the expected aggregate.
+    #[display("pointer verification error: {_0}")]
     Pointers(VerifyPointersError),
     /// An error occurred while verifying the coverage.
     #[display("coverage verification error: {_0}")]
@@ -277,6 +352,23 @@ pub enum VerificationError {
     Disproved,
 }
 
+/// Transaction and block context for [`Bundle::verify_for_block`].
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyParams<'adj> {
+    /// Sighash of the transaction carrying the bundle.
+    pub sighash: [u8; 32],
+    /// Wtxid of the transaction carrying the bundle, to which every adjunct
+    /// points.
+    pub wtxid: [u8; 64],
+    /// The adjuncts the bundle's stamp covers, each with the sighash of its
+    /// own transaction.
+    pub adjuncts: &'adj [(&'adj Bundle<PointerStamp>, [u8; 32])],
+    /// Anchors accepted by the block the bundle lands in.
+    pub anchor_policy: AnchorPolicy<'adj>,
+    /// Tachygrams published in the epochs whose anchors the block accepts.
+    pub recent_tachygrams: &'adj RecentTachygrams,
+}
+
 /// Errors that can occur while signing a bundle plan.
 #[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
 #[non_exhaustive]
@@ -284,12 +376,22 @@ pub enum PlanError {
     /// The signatures do not match the planned actions.
     #[display("planned actions do not match signed actions")]
     ActionSigMismatch,
+    /// The signature on the action with this descriptor does not verify over
+    /// the sighash, as when it was made before the plan was amended.
+    #[display("invalid signature on action {_0:?}")]
+    InvalidSignature(#[error(not(source))] action::Descriptor),
     /// The value balance overflows the representable range.
     #[display("value balance overflow")]
     BalanceOverflow,
 }
 
 /// A complete bundle plan, awaiting authorization.
+///
+/// Spends and outputs are held separately, but the order in which they were
+/// planned never reaches the wire: [`Plan::sign`] and
+/// [`Plan::apply_signatures`] emit actions in canonical descriptor order, and
+/// descriptors are indistinguishable from random bytes. No shuffling step is
+/// needed before computing the sighash.
 #[derive(Clone, Debug)]
 pub struct Plan {
     /// Spend action plans.
@@ -309,6 +411,31 @@ impl Plan {
         Self { spends, outputs }
     }
 
+    /// Amend the plan with another spend.
+    ///
+    /// The [`commitment`](Self::commitment) is derived on demand, so it must be
+    /// recomputed, and the transaction sighash with it. Signatures collected
+    /// for the unamended plan, even on actions the amendment left alone, do
+    /// not verify over the new sighash and are rejected by
+    /// [`apply_signatures`](Self::apply_signatures).
+    pub fn add_spend(&mut self, plan: action::Plan<effect::Spend>) {
+        self.spends.push(plan);
+    }
+
+    /// Amend the plan with another output. See [`add_spend`](Self::add_spend).
+    pub fn add_output(&mut self, plan: action::Plan<effect::Output>) {
+        self.outputs.push(plan);
+    }
+
+    /// Remove the action with the given descriptor, returning whether it was
+    /// present. See [`add_spend`](Self::add_spend).
+    pub fn remove(&mut self, descriptor: &action::Descriptor) -> bool {
+        let before = self.spends.len() + self.outputs.len();
+        self.spends.retain(|plan| plan.descriptor() != *descriptor);
+        self.outputs.retain(|plan| plan.descriptor() != *descriptor);
+        self.spends.len() + self.outputs.len() != before
+    }
+
     /// Iterate over all actions in the plan, mapping with the provided
     /// functions. It is equivalent to calling `transform_spend` for each spend
     /// and `transform_output` for each output.
@@ -366,6 +493,14 @@ impl Plan {
         ))
     }
 
+    /// Encoded size of the proof-stamped bundle this plan becomes, for fee
+    /// estimation before it is signed or proven.
+    #[must_use]
+    pub fn estimated_serialized_size(&self) -> usize {
+        body_len(self.spends.len() + self.outputs.len())
+            + ProofStamp::estimated_serialized_size(2 * self.spends.len() + self.outputs.len())
+    }
+
     /// Build a [`stamp::Plan`] from this bundle plan.
     ///
     /// Derives alpha from theta for each action and collects the proof
@@ -423,21 +558,79 @@ impl Plan {
             authorized.insert(plan.descriptor(), rsk.sign(rng, sighash));
         }
 
-        for plan in &self.outputs {
+        authorized.extend(self.sign_outputs(rng, sighash));
+
+        self.apply_signatures(rng, sighash, authorized)
+    }
+
+    /// Sign the spends that `ask` controls, for combination with signatures
+    /// from other custodians.
+    ///
+    /// Unlike [`sign`](Self::sign), a spend whose `rk` was not derived from
+    /// `ask` is skipped, so each custodian can be handed the whole plan. Merge
+    /// the results with [`sign_outputs`](Self::sign_outputs) and pass them to
+    /// [`apply_signatures`](Self::apply_signatures), which rejects an
+    /// incomplete set.
+    #[must_use]
+    pub fn sign_spends<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        sighash: &[u8; 32],
+        ask: &private::SpendAuthorizingKey,
+    ) -> BTreeMap<action::Descriptor, action::Signature> {
+        let mut authorized = BTreeMap::new();
+
+        for plan in &self.spends {
             let cm = plan.note.commitment();
-            let alpha = plan.theta.randomizer::<effect::Output>(cm);
-            let rsk = private::ActionSigningKey::new(&alpha);
-            authorized.insert(plan.descriptor(), rsk.sign(rng, sighash));
+            let alpha = plan.theta.randomizer::<effect::Spend>(cm);
+            let rsk = ask.derive_action_private(&alpha);
+            if rsk.derive_action_public() == plan.rk {
+                authorized.insert(plan.descriptor(), rsk.sign(rng, sighash));
+            }
         }
 
-        self.apply_signatures(rng, sighash, authorized)
+        authorized
+    }
+
+    /// Sign the outputs, which need no custody: each output's signing key is
+    /// its randomizer.
+    #[must_use]
+    pub fn sign_outputs<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        sighash: &[u8; 32],
+    ) -> BTreeMap<action::Descriptor, action::Signature> {
+        self.outputs
+            .iter()
+            .map(|plan| {
+                let alpha = plan
+                    .theta
+                    .randomizer::<effect::Output>(plan.note.commitment());
+                let rsk = private::ActionSigningKey::new(&alpha);
+                (plan.descriptor(), rsk.sign(rng, sighash))
+            })
+            .collect()
+    }
+
+    /// Descriptors of the planned actions that `authorized` does not yet sign.
+    #[must_use]
+    pub fn unsigned(
+        &self,
+        authorized: &BTreeMap<action::Descriptor, action::Signature>,
+    ) -> BTreeSet<action::Descriptor> {
+        self.descriptors()
+            .into_iter()
+            .filter(|desc| !authorized.contains_key(desc))
+            .collect()
     }
 
     /// Apply externally-produced action signatures and then sign the bundle
     /// with the [`private::BindingSigningKey`].
     ///
-    /// To confirm correct application, call [`Bundle::verify_signatures`] on
-    /// the return value.
+    /// # Errors
+    ///
+    /// Fails if `authorized` does not sign exactly the planned actions, or if
+    /// any of its signatures does not verify over `sighash`.
     pub fn apply_signatures<RNG: RngCore + CryptoRng>(
         &self,
         rng: &mut RNG,
@@ -451,6 +644,12 @@ impl Plan {
         if self.descriptors() != authorized.keys().copied().collect() {
             return Err(PlanError::ActionSigMismatch);
         }
+        if let Some(stale) = authorized
+            .iter()
+            .find(|entry| entry.0.rk.verify(sighash, entry.1).is_err())
+        {
+            return Err(PlanError::InvalidSignature(*stale.0));
+        }
         let actions = authorized.into_iter().map(Action::from).collect();
 
         let binding_sig = self.derive_bsk_private().sign(rng, sighash);
@@ -468,12 +667,7 @@ impl Bundle<Unproven> {
     /// Attach a proof stamp, producing a `Bundle<ProofStamp>`.
     #[must_use]
     pub fn stamp(self, stamp: ProofStamp) -> Bundle<ProofStamp> {
-        Bundle {
-            actions: self.actions,
-            value_balance: self.value_balance,
-            binding_sig: self.binding_sig,
-            stamp,
-        }
+        self.map_stamp(|Unproven| stamp)
     }
 }
 
@@ -481,12 +675,7 @@ impl Bundle<ProofStamp> {
     /// Replace the stamp with a wtxid pointer to a covering aggregate.
     #[must_use]
     pub fn strip(self, wtxid: PointerStamp) -> Bundle<PointerStamp> {
-        Bundle {
-            actions: self.actions,
-            value_balance: self.value_balance,
-            binding_sig: self.binding_sig,
-            stamp: wtxid,
-        }
+        self.map_stamp(|_stamp| wtxid)
     }
 
     /// Confirm `hStampActionsTachyon` represents the combined actions of this
@@ -571,17 +760,64 @@ impl Bundle<ProofStamp> {
 
         let action_digests = own_digests
             .chain(other_digests)
-            .collect::<Result<Vec<ActionDigest>, ActionDigestError>>()
-            .map_err(VerifyProofError::ActionDigest)?;
+            .enumerate()
+            .map(|(index, digest)| digest.map_err(|err| VerifyProofError::ActionDigest(index, err)))
+            .collect::<Result<Vec<ActionDigest>, VerifyProofError>>()?;
 
         self.stamp
             .verify_proof(rng, action_digests)
             .map_err(VerifyProofError::ProofSystem)
     }
 
+    /// Verify the bundle and the adjuncts it covers, completely.
+    ///
+    /// The single entry point for consensus. Checks, in order:
+    ///
+    /// 1. the stamp's anchor, against the landing block's [`AnchorPolicy`];
+    /// 2. the stamp's tachygrams, against those published in recent epochs;
+    /// 3. every signature of this bundle and its adjuncts, in one batch;
+    /// 4. the stamp, as [`verify`](Self::verify) does.
+    ///
+    /// A tachygram repeated by another stamp in the landing block itself is
+    /// caught by [`TachyonBlockData::check`](crate::TachyonBlockData::check).
+    pub fn verify_for_block<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        params: &VerifyParams<'_>,
+    ) -> Result<(), VerificationError> {
+        if !self.stamp.anchor.is_valid_for(&params.anchor_policy) {
+            return Err(VerificationError::AnchorOutOfRange);
+        }
+        if let Some(&tachygram) = self
+            .stamp
+            .tachygrams
+            .iter()
+            .find(|tachygram| params.recent_tachygrams.contains(tachygram))
+        {
+            return Err(VerificationError::RepeatedTachygram(tachygram));
+        }
+
+        let mut signatures = SignatureBatch::new();
+        signatures.add(self, &params.sighash);
+        for &(adjunct, sighash) in params.adjuncts {
+            signatures.add(adjunct, &sighash);
+        }
+        signatures
+            .verify(rng)
+            .map_err(BatchSignatureError::attribute)?;
+
+        let adjuncts: Vec<&Bundle<PointerStamp>> = params
+            .adjuncts
+            .iter()
+            .map(|&(adjunct, _)| adjunct)
+            .collect();
+        self.verify(rng, &params.wtxid, &adjuncts)
+    }
+
     /// Verify the proof stamp with given adjuncts.
     ///
-    /// Verification of signatures remains the responsibility of the caller.
+    /// Verification of signatures remains the responsibility of the caller;
+    /// [`verify_for_block`](Self::verify_for_block) performs both.
     pub fn verify<RNG: RngCore + CryptoRng>(
         &self,
         rng: &mut RNG,
@@ -594,17 +830,51 @@ impl Bundle<ProofStamp> {
         self.verify_pointers(wtxid, &adjuncts_dyn)
             .map_err(VerificationError::Pointers)?;
 
-        self.verify_coverage(&adjuncts_dyn)
-            .map_err(VerificationError::Coverage)?;
+        self.stamp.verify_covering(
+            rng,
+            iter::once(self.actions.as_slice())
+                .chain(adjuncts.iter().map(|&adj| adj.actions.as_slice())),
+        )
+    }
+}
 
+impl Bundle<PointerStamp> {
+    /// Check that `aggregate`, carried by the transaction with `wtxid`, can
+    /// cover this stripped bundle.
+    ///
+    /// Only what this bundle alone can show is checked: the aggregate's
+    /// coverage and proof depend on all its adjuncts. See
+    /// [`Transition::Strip`] for what remains.
+    pub fn requires(
+        &self,
+        aggregate: &Bundle<ProofStamp>,
+        wtxid: &[u8; 64],
+    ) -> Result<(), TransitionError> {
+        if &self.stamp.stamp_digest() != wtxid {
+            return Err(TransitionError::PointerMismatch);
+        }
+        if aggregate.is_autonome() {
+            return Err(TransitionError::NotAggregate);
+        }
+        let own: BTreeSet<action::Descriptor> = aggregate.descriptors().into_iter().collect();
         if self
-            .verify_proof(rng, &adjuncts_dyn)
-            .map_err(VerificationError::Proof)?
+            .actions
+            .iter()
+            .map(Action::descriptor)
+            .any(|descriptor| own.contains(&descriptor))
         {
-            Ok(())
-        } else {
-            Err(VerificationError::Disproved)
+            return Err(TransitionError::SharedAction);
         }
+        Ok(())
+    }
+
+    /// Give the bundle back a stamp of its own, replacing its pointer.
+    ///
+    /// Signatures carry over; the stamp is not checked. See
+    /// [`Transition::Restamp`] for what must be.
+    #[must_use]
+    pub fn restamp(self, stamp: ProofStamp) -> Bundle<ProofStamp> {
+        self.map_stamp(|_wtxid| stamp)
     }
 }
 
@@ -720,6 +990,13 @@ impl<S: StampState> Bundle<S> {
         self.stamp.write(&mut writer)
     }
 
+    /// Number of bytes [`write`](Self::write) produces, for packing bundles
+    /// under a block's size limit.
+    #[must_use]
+    pub fn weight(&self) -> usize {
+        body_len(self.actions.len()) + self.stamp.encoded_len()
+    }
+
     /// Tachyon's contribution to the transaction `auth_digest`.
     ///
     /// Commits the action signatures, the binding signature, and the stamp's
@@ -738,6 +1015,13 @@ impl<S: StampState> Bundle<S> {
     }
 }
 
+/// Encoded size of a bundle with `actions` actions, up to its stamp: the
+/// state byte, value balance, actions and their signatures, and binding
+/// signature.
+pub(crate) fn body_len(actions: usize) -> usize {
+    1 + 8 + serialization::compactsize_len(actions) + 128 * actions + 64
+}
+
 /// A Tachyon bundle in one of its valid wire states.
 ///
 /// The `Unproven` intermediate state is outside this enum because it has no
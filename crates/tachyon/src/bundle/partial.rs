@@ -0,0 +1,199 @@
+//! Partially-signed bundles, for authorization across devices.
+//!
+//! # Encoding
+//!
+//! | Name           | Format                  | Description                        |
+//! | -------------- | ----------------------- | ---------------------------------- |
+//! | `nSpends`      | compactsize             | number of planned spends           |
+//! | `vSpends`      | 200 * nSpends           | (rk, note, theta, rcv)             |
+//! | `nOutputs`     | compactsize             | number of planned outputs          |
+//! | `vOutputs`     | 200 * nOutputs          | (rk, note, theta, rcv)             |
+//! | `nSignatures`  | compactsize             | number of collected signatures     |
+//! | `vSignatures`  | 128 * nSignatures       | (cv, rk, sig), in descriptor order |
+//!
+//! A note is `pk || value || psi || rcm`, with the value as a little-endian
+//! u64. The spendability and nullifier PCDs are not carried: they stay with
+//! the prover, which receives the plan from the creator.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use corez::io::{self, Read, Write};
+use rand_core::{CryptoRng, RngCore};
+
+use super::{Bundle, Plan, PlanError};
+use crate::{action, keys::private, serialization, stamp::Unproven};
+
+/// A bundle plan in the course of authorization.
+///
+/// Carries a [`Plan`] and the action signatures collected so far between the
+/// parties that authorize it, in the manner of a PCZT for the other pools:
+///
+/// - the creator assembles the plan and wraps it with [`new`](Self::new);
+/// - each custodian signs the spends its key controls with
+///   [`sign_spends`](Self::sign_spends), and any party signs the outputs with
+///   [`sign_outputs`](Self::sign_outputs);
+/// - a combiner merges copies signed on different devices with
+///   [`combine`](Self::combine), and drops any signature made over another
+///   sighash with [`discard_invalid`](Self::discard_invalid);
+/// - the prover builds its [`stamp::Plan`](crate::stamp::Plan) from
+///   [`plan`](Self::plan), supplying the spend PCDs it holds;
+/// - [`finalize`](Self::finalize) adds the binding signature once every action
+///   is signed.
+///
+/// The encoding carries notes and trapdoors, so it must only pass between
+/// parties trusted with the transaction's contents.
+#[derive(Clone, Debug)]
+pub struct Partial {
+    plan: Plan,
+    authorized: BTreeMap<action::Descriptor, action::Signature>,
+}
+
+impl Partial {
+    /// Wrap a plan with no signatures collected.
+    #[must_use]
+    pub const fn new(plan: Plan) -> Self {
+        Self {
+            plan,
+            authorized: BTreeMap::new(),
+        }
+    }
+
+    /// The plan being authorized.
+    #[must_use]
+    pub const fn plan(&self) -> &Plan {
+        &self.plan
+    }
+
+    /// The action signatures collected so far.
+    #[must_use]
+    pub const fn signatures(&self) -> &BTreeMap<action::Descriptor, action::Signature> {
+        &self.authorized
+    }
+
+    /// Descriptors of the planned actions not yet signed.
+    #[must_use]
+    pub fn unsigned(&self) -> BTreeSet<action::Descriptor> {
+        self.plan.unsigned(&self.authorized)
+    }
+
+    /// Sign the spends that `ask` controls. See [`Plan::sign_spends`].
+    pub fn sign_spends<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+        sighash: &[u8; 32],
+        ask: &private::SpendAuthorizingKey,
+    ) {
+        let signed = self.plan.sign_spends(rng, sighash, ask);
+        self.authorized.extend(signed);
+    }
+
+    /// Sign the outputs. See [`Plan::sign_outputs`].
+    pub fn sign_outputs<RNG: RngCore + CryptoRng>(&mut self, rng: &mut RNG, sighash: &[u8; 32]) {
+        let signed = self.plan.sign_outputs(rng, sighash);
+        self.authorized.extend(signed);
+    }
+
+    /// Merge the signatures collected on another copy of the same plan.
+    ///
+    /// Where both copies sign an action, the signature already held is kept.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `other` carries a different plan.
+    pub fn combine(&mut self, other: Self) -> Result<(), PlanError> {
+        if self.plan.descriptors() != other.plan.descriptors() {
+            return Err(PlanError::ActionSigMismatch);
+        }
+        for (desc, sig) in other.authorized {
+            self.authorized.entry(desc).or_insert(sig);
+        }
+        Ok(())
+    }
+
+    /// Drop the signatures that do not verify over `sighash`, returning the
+    /// descriptors of the actions they were for.
+    ///
+    /// A custodian that signed a stale or wrong sighash leaves its actions
+    /// [`unsigned`](Self::unsigned) again, to be signed afresh.
+    pub fn discard_invalid(&mut self, sighash: &[u8; 32]) -> BTreeSet<action::Descriptor> {
+        let invalid: BTreeSet<action::Descriptor> = self
+            .authorized
+            .iter()
+            .filter(|entry| entry.0.rk.verify(sighash, entry.1).is_err())
+            .map(|entry| *entry.0)
+            .collect();
+        self.authorized.retain(|desc, _sig| !invalid.contains(desc));
+        invalid
+    }
+
+    /// Add the binding signature, producing the signed bundle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any action is unsigned, or any signature does not verify over
+    /// `sighash`, as [`Plan::apply_signatures`] does.
+    pub fn finalize<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        sighash: &[u8; 32],
+    ) -> Result<Bundle<Unproven>, PlanError> {
+        self.plan.apply_signatures(rng, sighash, self.authorized)
+    }
+
+    /// Read a partially-signed bundle. See the module-level encoding
+    /// documentation.
+    ///
+    /// Rejects duplicate signatures and signatures for unplanned actions.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        // lengths are attacker-controlled, so do not pre-allocate.
+        let mut plan = Plan::new(Vec::new(), Vec::new());
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            plan.add_spend(action::Plan::read(&mut reader)?);
+        }
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            plan.add_output(action::Plan::read(&mut reader)?);
+        }
+
+        let planned = plan.descriptors();
+        let mut authorized = BTreeMap::new();
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            let action = action::Action::read(&mut reader)?;
+            let desc = action.descriptor();
+            if !planned.contains(&desc) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "signature for unplanned action",
+                ));
+            }
+            if authorized.insert(desc, action.sig).is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "duplicate action signature",
+                ));
+            }
+        }
+
+        Ok(Self { plan, authorized })
+    }
+
+    /// Write a partially-signed bundle. See the module-level encoding
+    /// documentation.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_len(&mut writer, self.plan.spends.len())?;
+        for spend in &self.plan.spends {
+            spend.write(&mut writer)?;
+        }
+        serialization::write_len(&mut writer, self.plan.outputs.len())?;
+        for output in &self.plan.outputs {
+            output.write(&mut writer)?;
+        }
+        serialization::write_len(&mut writer, self.authorized.len())?;
+        for (&desc, &sig) in &self.authorized {
+            action::Action::from((desc, sig)).write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
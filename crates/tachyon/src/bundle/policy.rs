@@ -0,0 +1,104 @@
+//! Bundle shape policy.
+
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+
+/// Errors from checking a bundle shape against a [`Policy`].
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "intentional name")]
+pub enum PolicyError {
+    /// The bundle carries fewer actions than the policy requires.
+    #[display("too few actions")]
+    TooFewActions,
+    /// The bundle carries more actions than the policy allows.
+    #[display("too many actions")]
+    TooManyActions,
+    /// The action count is not a power of the policy's arity.
+    #[display("action count is not a power of the arity")]
+    Arity,
+    /// Reaching the required shape takes more padding than the policy allows.
+    #[display("too much padding")]
+    TooMuchPadding,
+}
+
+/// Constraints on the shape of a bundle.
+///
+/// Bundles of one shape are indistinguishable by their action count, so a
+/// wallet that holds every bundle to the same policy does not reveal how many
+/// payments it makes. [`Builder`](super::Builder) pads to the policy, and
+/// [`Bundle::check_policy`](super::Bundle::check_policy) checks any bundle
+/// against it.
+///
+/// The default pads to a power of two, as [`GroupBuilder`](crate::action::GroupBuilder)
+/// does, with no other limit.
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub struct Policy {
+    /// Fewest actions a bundle may carry.
+    pub min_actions: usize,
+    /// Most actions a bundle may carry.
+    pub max_actions: usize,
+    /// The action count must be a power of this arity. An arity below two
+    /// accepts any count.
+    pub arity: usize,
+    /// Most padding outputs, as a percentage of the padded action count.
+    ///
+    /// Only a builder can apply this limit: padding outputs are ordinary
+    /// outputs on the wire.
+    pub max_padding_percent: u8,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            min_actions: 1,
+            max_actions: usize::MAX,
+            arity: 2,
+            max_padding_percent: 100,
+        }
+    }
+}
+
+impl Policy {
+    /// Check an action count against the policy.
+    pub fn check(&self, actions: usize) -> Result<(), PolicyError> {
+        if actions < self.min_actions {
+            return Err(PolicyError::TooFewActions);
+        }
+        if actions > self.max_actions {
+            return Err(PolicyError::TooManyActions);
+        }
+        if self.round_up(actions) != Some(actions) {
+            return Err(PolicyError::Arity);
+        }
+        Ok(())
+    }
+
+    /// The action count that `real` actions are padded to: the least power of
+    /// the arity that is no less than `real` or the minimum.
+    pub fn padded_len(&self, real: usize) -> Result<usize, PolicyError> {
+        let target = self
+            .round_up(real.max(self.min_actions))
+            .ok_or(PolicyError::TooManyActions)?;
+        if target > self.max_actions {
+            return Err(PolicyError::TooManyActions);
+        }
+
+        let padding = (target - real).saturating_mul(100);
+        if padding > target.saturating_mul(usize::from(self.max_padding_percent)) {
+            return Err(PolicyError::TooMuchPadding);
+        }
+        Ok(target)
+    }
+
+    /// The least power of the arity no less than `len`, if representable.
+    fn round_up(&self, len: usize) -> Option<usize> {
+        if self.arity < 2 {
+            return Some(len);
+        }
+        let mut target = 1usize;
+        while target < len {
+            target = target.checked_mul(self.arity)?;
+        }
+        Some(target)
+    }
+}
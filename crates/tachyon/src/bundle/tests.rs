@@ -1,6 +1,6 @@
 #![allow(clippy::panic, clippy::too_many_lines, reason = "test code")]
 
-use alloc::{boxed::Box, string::ToString as _, vec, vec::Vec};
+use alloc::{string::ToString as _, vec, vec::Vec};
 use core::cmp::Reverse;
 
 use pasta_curves::Fp;
@@ -9,15 +9,18 @@ use rand::{SeedableRng as _, rngs::StdRng};
 
 use super::*;
 use crate::{
+    TachyonBlockData,
+    aggregate::Aggregate,
+    block::RecentTachygrams,
     constants::{EPOCH_SIZE, MAX_MONEY},
     digest::blake2b::{COMMIT_NO_BUNDLE, action_descriptor_digest, bundle_commitment},
     entropy::ActionEntropy,
     fixtures::{
-        PoolSim, WalletSim, build_autonome, build_output_plan, build_output_stamp,
-        forge_overlapping_merge, mock_sighash, mock_wtxid, random_block, random_block_with,
-        shared_sk, spend_witness,
+        PoolSim, WalletSim, build_autonome, build_output_bundle, build_output_plan,
+        build_output_stamp, forge_overlapping_merge, mock_sighash, mock_wtxid, random_block,
+        random_block_with, shared_sk, spend_witness,
     },
-    primitives::{BlockHeight, Tachygram},
+    primitives::{BlockHeight, EpochIndex, Tachygram},
     value,
 };
 
@@ -83,6 +86,22 @@ fn stripped_bundle_retains_signatures() {
     adjunct.verify_signatures(&sighash).unwrap();
 }
 
+/// `map_stamp` carries the authorized body across a state change.
+#[test]
+fn map_stamp_preserves_bundle_body() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let bundle = build_autonome(rng, &wallet, 1000, 700);
+
+    let mut stamp = None;
+    let unproven = bundle.clone().map_stamp(|proof_stamp| {
+        stamp = Some(proof_stamp);
+        Unproven
+    });
+    let restamped = unproven.stamp(stamp.unwrap());
+    assert_eq!(restamped, bundle);
+}
+
 #[test]
 fn plan_commitment_matches_bundle_commitment() {
     let rng = &mut StdRng::seed_from_u64(0);
@@ -101,6 +120,11 @@ fn plan_commitment_matches_bundle_commitment() {
         .stamp(stamp);
 
     assert_eq!(bundle_plan.commitment().unwrap(), bundle.commitment());
+    assert_eq!(
+        bundle_plan.estimated_serialized_size(),
+        bundle.weight(),
+        "a plan's size is known before proving"
+    );
 }
 
 /// The output's `rk` is corrupted to an unrelated (but known) key after
@@ -147,7 +171,7 @@ fn actions_signed_despite_wrong_rk_fail_verification() {
     let err = bundle
         .verify_signatures(&mock_sighash(bundle.commitment()))
         .unwrap_err();
-    let SignatureError::Action(_) = err else {
+    let SignatureError::Action(_, _) = err else {
         panic!("expected SignatureError::Action, got {err:?}");
     };
 }
@@ -190,7 +214,7 @@ fn actions_signed_by_wrong_rsk_fail_verification() {
     let err = bundle
         .verify_signatures(&mock_sighash(bundle.commitment()))
         .unwrap_err();
-    let SignatureError::Action(_) = err else {
+    let SignatureError::Action(_, _) = err else {
         panic!("expected SignatureError::Action, got {err:?}");
     };
 }
@@ -253,7 +277,7 @@ fn apply_signatures_rejects_wrong_sig_count() {
 }
 
 #[test]
-fn apply_signatures_with_shuffled_sigs_fails_verification() {
+fn apply_signatures_rejects_shuffled_sigs() {
     let rng = &mut StdRng::seed_from_u64(0);
     let wallet = WalletSim::random(rng);
     let ask = wallet.sk.derive_auth_private();
@@ -272,21 +296,17 @@ fn apply_signatures_with_shuffled_sigs_fails_verification() {
         .map(|action| action.sig)
         .collect();
 
-    // shuffled assembly still succeeds
+    // each signature is now paired with the other action
     sigs.reverse();
     let authorized = plan.descriptors().into_iter().zip(sigs).collect();
 
-    let bundle = plan
+    let err = plan
         .apply_signatures(rng, &mock_sighash(plan.commitment().unwrap()), authorized)
-        .expect("assembly succeeds regardless of sig order");
-
-    // but the mismatched pairing fails verification.
-    let err = bundle
-        .verify_signatures(&mock_sighash(bundle.commitment()))
         .unwrap_err();
-    let SignatureError::Action(_) = err else {
-        panic!("expected SignatureError::Action, got {err:?}");
-    };
+    assert!(
+        matches!(err, PlanError::InvalidSignature(_)),
+        "expected PlanError::InvalidSignature, got {err:?}"
+    );
 }
 
 /// Permuting a bundle's actions changes its commitment, so a sighash
@@ -400,6 +420,224 @@ fn sign_and_apply_signatures_handle_one_sided_and_empty_plans() {
         .expect("empty bundle via apply_signatures verifies");
 }
 
+/// Two custodians each sign only their own spends; the combined signatures
+/// complete the plan.
+#[test]
+fn custodians_sign_disjoint_spends() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let alice = WalletSim::random(rng);
+    let bob = WalletSim::random(rng);
+    let alice_ask = alice.sk.derive_auth_private();
+    let bob_ask = bob.sk.derive_auth_private();
+
+    let alice_spend = spend_plan_at(rng, &alice, &alice_ask, 300);
+    let bob_spend = spend_plan_at(rng, &bob, &bob_ask, 200);
+    let (_rcv, _alpha, output) = build_output_plan(rng, alice.random_note(400));
+    let plan = Plan::new(vec![alice_spend, bob_spend], vec![output]);
+    let sighash = mock_sighash(plan.commitment().unwrap());
+
+    let mut authorized = plan.sign_spends(rng, &sighash, &alice_ask);
+    assert_eq!(authorized.len(), 1);
+    authorized.extend(plan.sign_outputs(rng, &sighash));
+    assert_eq!(
+        plan.unsigned(&authorized),
+        BTreeSet::from([bob_spend.descriptor()])
+    );
+    assert_eq!(
+        plan.apply_signatures(rng, &sighash, authorized.clone())
+            .unwrap_err(),
+        PlanError::ActionSigMismatch
+    );
+
+    authorized.extend(plan.sign_spends(rng, &sighash, &bob_ask));
+    assert!(plan.unsigned(&authorized).is_empty(), "all actions signed");
+    plan.apply_signatures(rng, &sighash, authorized)
+        .unwrap()
+        .verify_signatures(&sighash)
+        .unwrap();
+}
+
+/// A partially-signed bundle survives the wire between custodians, and the
+/// combined copies finalize to a verifying bundle.
+#[test]
+fn partial_bundle_round_trips_between_custodians() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let alice = WalletSim::random(rng);
+    let bob = WalletSim::random(rng);
+    let alice_ask = alice.sk.derive_auth_private();
+    let bob_ask = bob.sk.derive_auth_private();
+
+    let alice_spend = spend_plan_at(rng, &alice, &alice_ask, 300);
+    let bob_spend = spend_plan_at(rng, &bob, &bob_ask, 200);
+    let (_rcv, _alpha, output) = build_output_plan(rng, alice.random_note(400));
+    let created = Partial::new(Plan::new(vec![alice_spend, bob_spend], vec![output]));
+    let mut buf = Vec::new();
+    created.write(&mut buf).expect("write");
+
+    let mut at_alice = Partial::read(&*buf).expect("read");
+    let sighash = mock_sighash(at_alice.plan().commitment().unwrap());
+    at_alice.sign_spends(rng, &sighash, &alice_ask);
+    at_alice.sign_outputs(rng, &sighash);
+    let mut at_bob = Partial::read(&*buf).expect("read");
+    at_bob.sign_spends(rng, &sighash, &bob_ask);
+    assert_eq!(at_bob.unsigned().len(), 2);
+
+    let mut signed = Vec::new();
+    at_bob.write(&mut signed).expect("write");
+    at_alice
+        .combine(Partial::read(&*signed).expect("read"))
+        .unwrap();
+    assert!(at_alice.unsigned().is_empty(), "all actions signed");
+
+    let other = Partial::new(Plan::new(vec![alice_spend], vec![output]));
+    assert_eq!(
+        at_alice.clone().combine(other).unwrap_err(),
+        PlanError::ActionSigMismatch
+    );
+
+    at_alice
+        .finalize(rng, &sighash)
+        .unwrap()
+        .verify_signatures(&sighash)
+        .unwrap();
+}
+
+/// A combiner drops signatures made over another sighash, leaving their
+/// actions to be signed again.
+#[test]
+fn partial_bundle_discards_signatures_over_another_sighash() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let ask = wallet.sk.derive_auth_private();
+    let spend = spend_plan_at(rng, &wallet, &ask, 300);
+    let (_rcv, _alpha, output) = build_output_plan(rng, wallet.random_note(300));
+    let mut partial = Partial::new(Plan::new(vec![spend], vec![output]));
+    let sighash = mock_sighash(partial.plan().commitment().unwrap());
+
+    partial.sign_outputs(rng, &sighash);
+    partial.sign_spends(rng, &[0xAB; 32], &ask);
+    assert!(partial.unsigned().is_empty(), "all actions signed");
+
+    let invalid = partial.discard_invalid(&sighash);
+    assert_eq!(invalid, BTreeSet::from([spend.descriptor()]));
+    assert_eq!(partial.unsigned(), invalid);
+    assert!(partial.discard_invalid(&sighash).is_empty(), "rest verify");
+
+    partial.sign_spends(rng, &sighash, &ask);
+    partial
+        .finalize(rng, &sighash)
+        .unwrap()
+        .verify_signatures(&sighash)
+        .unwrap();
+}
+
+/// A partially-signed bundle rejects signatures for actions it does not plan.
+#[test]
+fn partial_bundle_read_rejects_unplanned_signature() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let ask = wallet.sk.derive_auth_private();
+    let spend = spend_plan_at(rng, &wallet, &ask, 300);
+    let (_rcv, _alpha, output) = build_output_plan(rng, wallet.random_note(300));
+    let mut spend_bytes = Vec::new();
+    spend.write(&mut spend_bytes).expect("write");
+    let mut partial = Partial::new(Plan::new(vec![spend], vec![output]));
+    let sighash = mock_sighash(partial.plan().commitment().unwrap());
+    partial.sign_outputs(rng, &sighash);
+
+    let mut buf = Vec::new();
+    partial.write(&mut buf).expect("write");
+    // drop the output from the plan, keeping its signature: the spends run
+    // up to the output count, and the lone signed action ends the encoding
+    let spends_end = serialization::compactsize_len(1) + spend_bytes.len();
+    let signed_output = &buf[buf.len() - action::Action::SIZE..];
+    let mut tampered = buf[..spends_end].to_vec();
+    serialization::write_len(&mut tampered, 0).expect("write");
+    serialization::write_len(&mut tampered, 1).expect("write");
+    tampered.extend_from_slice(signed_output);
+
+    let err = Partial::read(&*tampered).unwrap_err();
+    assert_eq!(err.to_string(), "signature for unplanned action");
+}
+
+/// Amending a plan changes its commitment, and signatures collected before
+/// the amendment are rejected.
+#[test]
+fn amended_plan_rejects_stale_signatures() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let ask = wallet.sk.derive_auth_private();
+
+    let spend = spend_plan_at(rng, &wallet, &ask, 1000);
+    let (_rcv, _alpha, output) = build_output_plan(rng, wallet.random_note(700));
+    let mut plan = Plan::new(vec![spend], vec![output]);
+    let stale_sighash = mock_sighash(plan.commitment().unwrap());
+    let stale = plan.sign(rng, &stale_sighash, &ask).unwrap();
+    let stale_sigs: BTreeMap<_, _> = stale
+        .actions
+        .iter()
+        .map(|action| (action.descriptor(), action.sig))
+        .collect();
+
+    // fee bump: replace the output with a smaller one
+    assert!(plan.remove(&output.descriptor()), "output was planned");
+    assert!(!plan.remove(&output.descriptor()), "output already removed");
+    let (_rcv, _alpha, bumped) = build_output_plan(rng, wallet.random_note(690));
+    plan.add_output(bumped);
+
+    let sighash = mock_sighash(plan.commitment().unwrap());
+    assert_ne!(sighash, stale_sighash);
+    let err = plan
+        .apply_signatures(rng, &sighash, stale_sigs.clone())
+        .unwrap_err();
+    assert_eq!(err, PlanError::ActionSigMismatch);
+
+    // the spend is unchanged, but its signature is over the stale sighash
+    let mut mixed = plan.sign_outputs(rng, &sighash);
+    mixed.insert(spend.descriptor(), stale_sigs[&spend.descriptor()]);
+    let err = plan.apply_signatures(rng, &sighash, mixed).unwrap_err();
+    assert_eq!(err, PlanError::InvalidSignature(spend.descriptor()));
+
+    plan.sign(rng, &sighash, &ask)
+        .unwrap()
+        .verify_signatures(&sighash)
+        .unwrap();
+}
+
+/// The signed action order depends only on the descriptors, not on the order
+/// or kind of the planned actions.
+#[test]
+fn signed_action_order_hides_plan_order() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let ask = wallet.sk.derive_auth_private();
+
+    let spends = vec![
+        spend_plan_at(rng, &wallet, &ask, 300),
+        spend_plan_at(rng, &wallet, &ask, 200),
+    ];
+    let outputs = vec![
+        build_output_plan(rng, wallet.random_note(250)).2,
+        build_output_plan(rng, wallet.random_note(150)).2,
+    ];
+
+    let forward = Plan::new(spends.clone(), outputs.clone());
+    let backward = Plan::new(
+        spends.into_iter().rev().collect(),
+        outputs.into_iter().rev().collect(),
+    );
+    let sighash = mock_sighash(forward.commitment().unwrap());
+    assert_eq!(forward.commitment(), backward.commitment());
+
+    let forward_bundle = forward.sign(rng, &sighash, &ask).unwrap();
+    let backward_bundle = backward.sign(rng, &sighash, &ask).unwrap();
+
+    let order = forward_bundle.descriptors();
+    assert!(order.is_sorted(), "actions are in canonical order");
+    assert_eq!(order, backward_bundle.descriptors());
+    backward_bundle.verify_signatures(&sighash).unwrap();
+}
+
 #[test]
 fn no_bundle_commitment_differs_from_empty_bundle() {
     let empty_plan = Plan::new(alloc::vec![], alloc::vec![]);
@@ -484,8 +722,8 @@ fn double_spend_obvious() {
     let action = Action::from((descriptor, sig));
     let binding_sig = private::BindingSigningKey::from([rcv, rcv]).sign(rng, &sighash);
 
-    // Forge the stamp by merging one output stamp with itself: the merge proof
-    // commits to the doubled action and tachygram multisets.
+    // Merging one output stamp with itself would commit to the doubled action
+    // and tachygram multisets, but the merge step refuses the shared tachygram.
     let (tachygrams, stamp_anchor, proof) =
         ProofStamp::prove_output(rng, rcv, alpha, note, anchor).expect("prove_output");
     let output_stamp = ProofStamp {
@@ -496,24 +734,30 @@ fn double_spend_obvious() {
         anchor: stamp_anchor,
         proof,
     };
-    let evil_pcd = forge_overlapping_merge(
+    let forge_err = forge_overlapping_merge(
         rng,
         (&output_stamp, &vec![descriptor]),
         (&output_stamp, &vec![descriptor]),
+    )
+    .expect_err("the merge step must refuse the shared tachygram");
+    let ragu::Error::InvalidWitness(inner) = forge_err else {
+        panic!("expected InvalidWitness, got {forge_err:?}");
+    };
+    assert_eq!(
+        inner.to_string(),
+        "MergeStamp: tachygram sets must be disjoint"
     );
+
+    // Republish the single output stamp with its coverage forged to the
+    // duplicated action set instead.
     let coverage = {
         let mut desc_bytes: Vec<[u8; 64]> = vec![descriptor, descriptor].into_iter().collect();
         desc_bytes.sort_unstable();
         action_descriptor_digest(&desc_bytes)
     };
-
-    // A tachygram set with duplicated elements wouldn't be accepted by
-    // consensus actors. (see `read_rejects_duplicate_tachygrams`)
     let stamp = ProofStamp {
         coverage,
-        anchor: evil_pcd.data().2,
-        tachygrams: output_stamp.tachygrams.iter().copied().collect(),
-        proof: Box::new(evil_pcd.proof().clone()),
+        ..output_stamp
     };
 
     let bundle = Bundle {
@@ -552,12 +796,8 @@ fn double_spend_obvious() {
         panic!("expected DuplicateActions, got {dup_err:?}");
     };
 
-    // The proof independently rejects it, but on the tachygram accumulator, not
-    // the action one: the forged merge commits to the doubled action multiset
-    // (x-d)^2 AND the doubled tachygram (x-cm)^2, so reconstructing the action
-    // set from [d, d] matches. A wire-valid stamp must carry a canonical
-    // deduplicated tachygram set (see `read_rejects_duplicate_tachygrams`),
-    // whose (x-cm) cannot reconstruct the doubled tachygram the proof commits to.
+    // The proof independently rejects it: reconstructing the action set from
+    // [d, d] gives (x-d)^2, which the single-output proof does not commit to.
     let digests: Vec<ActionDigest> = decoded
         .descriptors()
         .iter()
@@ -568,7 +808,7 @@ fn double_spend_obvious() {
             .stamp
             .verify_proof(rng, digests)
             .expect("proof system verification"),
-        "the deduplicated tachygram set cannot reconstruct the doubled proof"
+        "the single-output proof cannot cover the duplicated action"
     );
 }
 
@@ -850,7 +1090,7 @@ fn innocent_aggregate_from_two_autonomes() {
         let innocent_plan = Plan::new(alloc::vec![], alloc::vec![]);
         let innocent_sighash = mock_sighash(innocent_plan.commitment().unwrap());
 
-        let stamp = ProofStamp::merge(rng, (stamp_a, descriptors_a), (stamp_b, descriptors_b))
+        let stamp = ProofStamp::merge(rng, (stamp_a, &descriptors_a), (stamp_b, &descriptors_b))
             .expect("merge");
 
         Bundle {
@@ -952,13 +1192,14 @@ fn based_aggregate_with_two_adjuncts() {
 
     let innocent_descriptors: BTreeSet<action::Descriptor> =
         descriptors_a.union(&descriptors_b).copied().collect();
-    let innocent_stamp = ProofStamp::merge(rng, (stamp_a, descriptors_a), (stamp_b, descriptors_b))
-        .expect("innocent merge");
+    let innocent_stamp =
+        ProofStamp::merge(rng, (stamp_a, &descriptors_a), (stamp_b, &descriptors_b))
+            .expect("innocent merge");
 
     let based_stamp = ProofStamp::merge(
         rng,
-        (becomes_based.stamp, based_descriptors),
-        (innocent_stamp, innocent_descriptors),
+        (becomes_based.stamp, &based_descriptors),
+        (innocent_stamp, &innocent_descriptors),
     )
     .expect("based merge");
 
@@ -980,8 +1221,8 @@ fn based_aggregate_with_two_adjuncts() {
         "based aggregate proof must verify against its adjuncts"
     );
 
-    // `verify` composes the pointer, coverage, and proof checks against the
-    // covering wtxid. Signatures are verified separately above.
+    // `verify` composes the pointer, coverage, and proof checks against
+    // the covering wtxid. Signatures are verified separately above.
     assert!(
         becomes_based.is_aggregate(),
         "a based aggregate does not cover its own actions alone"
@@ -990,6 +1231,129 @@ fn based_aggregate_with_two_adjuncts() {
         .verify(rng, &wtxid_bytes, &[&adjunct_a, &adjunct_b])
         .expect("based aggregate fully verifies against its adjuncts");
 
+    // `verify_for_block` adds every signature, each adjunct's over its own sighash.
+    let adjuncts = [
+        (&adjunct_a, mock_sighash(adjunct_a.commitment())),
+        (&adjunct_b, mock_sighash(adjunct_b.commitment())),
+    ];
+    let epoch_of = |candidate: &Anchor| (*candidate == anchor).then_some(spend_epoch);
+    let recent_tachygrams = RecentTachygrams::new(1);
+    let params = VerifyParams {
+        sighash,
+        wtxid: wtxid_bytes,
+        adjuncts: &adjuncts,
+        anchor_policy: AnchorPolicy {
+            current_epoch: spend_epoch,
+            window: 1,
+            epoch_of: &epoch_of,
+        },
+        recent_tachygrams: &recent_tachygrams,
+    };
+    becomes_based
+        .verify_for_block(rng, &params)
+        .expect("based aggregate and adjunct signatures verify");
+    {
+        let misattributed = [adjuncts[0], (&adjunct_b, sighash)];
+        let err = becomes_based
+            .verify_for_block(
+                rng,
+                &VerifyParams {
+                    adjuncts: &misattributed,
+                    ..params
+                },
+            )
+            .expect_err("an adjunct signed over another sighash must be rejected");
+        let VerificationError::AdjunctSignature(1, _) = err else {
+            panic!("expected AdjunctSignature on adjunct 1, got {err:?}");
+        };
+    }
+
+    // Streaming reaches the same verdict, one adjunct at a time.
+    {
+        let mut stream = StreamingVerifier::new(
+            rng,
+            &becomes_based,
+            &sighash,
+            wtxid_bytes,
+            &params.anchor_policy,
+        )
+        .expect("valid wtxid");
+        for &(adjunct, adjunct_sighash) in &adjuncts {
+            stream
+                .push_adjunct(rng, adjunct, &adjunct_sighash)
+                .expect("adjunct points to the aggregate");
+        }
+        stream.finish(rng).expect("streamed aggregate verifies");
+
+        let mut missing = StreamingVerifier::new(
+            rng,
+            &becomes_based,
+            &sighash,
+            wtxid_bytes,
+            &params.anchor_policy,
+        )
+        .expect("valid wtxid");
+        missing
+            .push_adjunct(rng, &adjunct_a, &adjuncts[0].1)
+            .expect("adjunct points to the aggregate");
+        let err = missing
+            .finish(rng)
+            .expect_err("a missing adjunct must mismatch coverage");
+        let VerificationError::Coverage(VerifyCoverageError::StampActionsMismatch) = err else {
+            panic!("expected StampActionsMismatch, got {err:?}");
+        };
+
+        let mut repeated = StreamingVerifier::new(
+            rng,
+            &becomes_based,
+            &sighash,
+            wtxid_bytes,
+            &params.anchor_policy,
+        )
+        .expect("valid wtxid");
+        repeated
+            .push_adjunct(rng, &adjunct_a, &adjuncts[0].1)
+            .expect("adjunct points to the aggregate");
+        let err = repeated
+            .push_adjunct(rng, &adjunct_a, &adjuncts[0].1)
+            .expect_err("an adjunct pushed twice must be rejected");
+        let VerificationError::Coverage(VerifyCoverageError::DuplicateActions) = err else {
+            panic!("expected DuplicateActions, got {err:?}");
+        };
+
+        let mut misattributed = StreamingVerifier::new(
+            rng,
+            &becomes_based,
+            &sighash,
+            wtxid_bytes,
+            &params.anchor_policy,
+        )
+        .expect("valid wtxid");
+        misattributed
+            .push_adjunct(rng, &adjunct_a, &adjuncts[0].1)
+            .expect("adjunct points to the aggregate");
+        misattributed
+            .push_adjunct(rng, &adjunct_b, &sighash)
+            .expect("adjunct points to the aggregate");
+        let err = misattributed
+            .finish(rng)
+            .expect_err("an adjunct signed over another sighash must be rejected");
+        let VerificationError::AdjunctSignature(1, _) = err else {
+            panic!("expected AdjunctSignature on adjunct 1, got {err:?}");
+        };
+
+        let late = AnchorPolicy {
+            current_epoch: spend_epoch.next().next(),
+            ..params.anchor_policy
+        };
+        let err = StreamingVerifier::new(rng, &becomes_based, &sighash, wtxid_bytes, &late)
+            .expect_err("an anchor from before the window must be rejected");
+        assert!(
+            matches!(err, VerificationError::AnchorOutOfRange),
+            "expected AnchorOutOfRange, got {err:?}"
+        );
+    }
+
     // A wtxid the adjuncts were not stripped with: the pointer check fails first.
     {
         let foreign = [0x5au8; 64];
@@ -1011,7 +1375,8 @@ fn based_aggregate_with_two_adjuncts() {
         };
     }
 
-    // A corrupted action signature is caught by `verify_signatures`, not `verify`.
+    // A corrupted action signature is caught by `verify_signatures`, not
+    // `verify`.
     {
         let mut tampered = becomes_based.clone();
         let mut sig_bytes: [u8; 64] = tampered.actions[0].sig.0.into();
@@ -1020,14 +1385,15 @@ fn based_aggregate_with_two_adjuncts() {
         let err = tampered
             .verify_signatures(&sighash)
             .expect_err("a corrupted action signature must fail signature verification");
-        let SignatureError::Action(_) = err else {
+        let SignatureError::Action(_, _) = err else {
             panic!("expected SignatureError::Action, got {err:?}");
         };
     }
 }
 
-/// `verify` on an autonome (no adjuncts). Signatures are checked separately by
-/// `verify_signatures`, which also catches a corrupted binding signature. With
+/// `verify` on an autonome (no adjuncts). Signatures are checked
+/// separately by `verify_signatures` or together by `verify`, which also catch
+/// a corrupted binding signature. With
 /// no adjuncts the `wtxid` is not matched, but must still be a valid nonzero
 /// aggregate id.
 #[test]
@@ -1056,6 +1422,261 @@ fn autonome_verify_composes_all_checks() {
     let SignatureError::Binding(_) = err else {
         panic!("expected SignatureError::Binding, got {err:?}");
     };
+
+    let anchor = bundle.stamp.anchor;
+    let epoch_of = |candidate: &Anchor| (*candidate == anchor).then_some(EpochIndex(0));
+    let recent_tachygrams = RecentTachygrams::new(1);
+    let params = VerifyParams {
+        sighash,
+        wtxid,
+        adjuncts: &[],
+        anchor_policy: AnchorPolicy {
+            current_epoch: EpochIndex(0),
+            window: 1,
+            epoch_of: &epoch_of,
+        },
+        recent_tachygrams: &recent_tachygrams,
+    };
+    bundle
+        .verify_for_block(rng, &params)
+        .expect("honest autonome verifies in one call");
+    let err = bundle
+        .verify_for_block(
+            rng,
+            &VerifyParams {
+                anchor_policy: AnchorPolicy {
+                    current_epoch: EpochIndex(2),
+                    ..params.anchor_policy
+                },
+                ..params
+            },
+        )
+        .expect_err("an anchor from before the window must be rejected");
+    assert!(
+        matches!(err, VerificationError::AnchorOutOfRange),
+        "expected AnchorOutOfRange, got {err:?}"
+    );
+
+    // Once a block has published the stamp, its tachygrams cannot land again.
+    let mut block = TachyonBlockData {
+        prev_anchor: anchor,
+        anchor,
+        aggregates: vec![Aggregate::new(bundle.clone(), vec![])],
+    };
+    block.anchor = block.next_anchor();
+    let mut published = RecentTachygrams::new(1);
+    published
+        .apply(EpochIndex(0), &block)
+        .expect("the block publishes the stamp");
+    let err = bundle
+        .verify_for_block(
+            rng,
+            &VerifyParams {
+                recent_tachygrams: &published,
+                ..params
+            },
+        )
+        .expect_err("a recently published tachygram must be rejected");
+    assert!(
+        matches!(err, VerificationError::RepeatedTachygram(_)),
+        "expected RepeatedTachygram, got {err:?}"
+    );
+    let err = tampered
+        .verify_for_block(rng, &params)
+        .expect_err("a corrupted binding signature must fail verification");
+    let VerificationError::Signature(SignatureError::Binding(_)) = err else {
+        panic!("expected Signature(Binding), got {err:?}");
+    };
+}
+
+/// The builder returns change, pads to a power of two, and produces a bundle
+/// that verifies.
+#[test]
+fn builder_produces_verifying_bundle() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let recipient = WalletSim::random(rng);
+
+    let spend_note = wallet.random_note(1000);
+    let mut pool = PoolSim::genesis(rng);
+    pool.mine(random_block_with(rng, &[vec![spend_note.commitment()]], 50));
+    let height = pool.height();
+    let spendable = wallet.fresh_spend(rng, &pool, height, &spend_note);
+    let range = wallet.derived_range(rng, &spend_note, height.epoch(), 2);
+    let anchor = spendable.data().2;
+
+    let mut builder = Builder::new(wallet.pak.derive_payment_key(), 100);
+    builder
+        .add_spend(spend_note, ActionEntropy::random(rng), range, spendable)
+        .add_output(
+            recipient.pak.derive_payment_key(),
+            value::Positive::try_from(600u64).unwrap(),
+        );
+    let (bundle, notes) = builder
+        .build(
+            rng,
+            &wallet.sk.derive_auth_private(),
+            &wallet.pak,
+            anchor,
+            mock_sighash,
+        )
+        .unwrap();
+
+    // spend, payment, and the change split in two to pad to four actions
+    assert_eq!(bundle.actions.len(), 4);
+    assert_eq!(notes.len(), 3);
+    assert_eq!(u64::from(notes[1].value) + u64::from(notes[2].value), 300);
+    assert_eq!(bundle.value_balance, value::Balance::try_from(100).unwrap());
+
+    let wtxid: [u8; 64] = mock_wtxid(&bundle).into();
+    bundle
+        .verify_signatures(&mock_sighash(bundle.commitment()))
+        .unwrap();
+    bundle.verify(rng, &wtxid, &[]).unwrap();
+}
+
+/// Outputs and padding that exceed the spends are rejected before proving.
+#[test]
+fn builder_rejects_insufficient_funds() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+
+    let mut builder = Builder::new(wallet.pak.derive_payment_key(), 0);
+    builder.add_output(
+        wallet.pak.derive_payment_key(),
+        value::Positive::try_from(1u64).unwrap(),
+    );
+    let err = builder
+        .build(
+            rng,
+            &wallet.sk.derive_auth_private(),
+            &wallet.pak,
+            PoolSim::genesis(rng).anchor(),
+            mock_sighash,
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, BuildError::InsufficientFunds),
+        "expected InsufficientFunds, got {err:?}"
+    );
+}
+
+/// A remainder the policy leaves no room to return is an error, not extra fee.
+#[test]
+fn builder_rejects_unreturnable_change() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+
+    let spend_note = wallet.random_note(1000);
+    let mut pool = PoolSim::genesis(rng);
+    pool.mine(random_block_with(rng, &[vec![spend_note.commitment()]], 50));
+    let height = pool.height();
+    let spendable = wallet.fresh_spend(rng, &pool, height, &spend_note);
+    let range = wallet.derived_range(rng, &spend_note, height.epoch(), 2);
+    let anchor = spendable.data().2;
+
+    // spend and payment fill the policy; change would need a third action
+    let mut builder = Builder::new(wallet.pak.derive_payment_key(), 100);
+    builder
+        .policy(Policy {
+            max_actions: 2,
+            ..Policy::default()
+        })
+        .add_spend(spend_note, ActionEntropy::random(rng), range, spendable)
+        .add_output(
+            wallet.pak.derive_payment_key(),
+            value::Positive::try_from(600u64).unwrap(),
+        );
+    let err = builder
+        .build(
+            rng,
+            &wallet.sk.derive_auth_private(),
+            &wallet.pak,
+            anchor,
+            mock_sighash,
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, BuildError::NoRoomForChange),
+        "expected NoRoomForChange, got {err:?}"
+    );
+}
+
+/// A builder fails before proving when no shape satisfies its policy.
+#[test]
+fn builder_rejects_policy_violation() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+
+    let mut builder = Builder::new(wallet.pak.derive_payment_key(), 0);
+    builder.policy(Policy {
+        max_actions: 1,
+        ..Policy::default()
+    });
+    for _ in 0..2 {
+        builder.add_output(
+            wallet.pak.derive_payment_key(),
+            value::Positive::try_from(1u64).unwrap(),
+        );
+    }
+    let err = builder
+        .build(
+            rng,
+            &wallet.sk.derive_auth_private(),
+            &wallet.pak,
+            PoolSim::genesis(rng).anchor(),
+            mock_sighash,
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, BuildError::Policy(PolicyError::TooManyActions)),
+        "expected TooManyActions, got {err:?}"
+    );
+}
+
+/// Padding targets respect the minimum, arity, maximum, and padding limit.
+#[test]
+fn policy_pads_to_shape() {
+    let policy = Policy {
+        min_actions: 4,
+        max_actions: 16,
+        arity: 4,
+        max_padding_percent: 75,
+    };
+    assert_eq!(policy.padded_len(1), Ok(4));
+    assert_eq!(policy.padded_len(5), Ok(16));
+    assert_eq!(policy.padded_len(17), Err(PolicyError::TooManyActions));
+    assert_eq!(
+        Policy {
+            max_padding_percent: 50,
+            ..policy
+        }
+        .padded_len(1),
+        Err(PolicyError::TooMuchPadding)
+    );
+
+    assert_eq!(policy.check(1), Err(PolicyError::TooFewActions));
+    assert_eq!(policy.check(8), Err(PolicyError::Arity));
+    assert_eq!(policy.check(64), Err(PolicyError::TooManyActions));
+    assert_eq!(policy.check(16), Ok(()));
+}
+
+/// A bundle is checked against a policy by its action count alone.
+#[test]
+fn bundle_check_policy() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let bundle = build_autonome(rng, &wallet, 1000, 700);
+    assert_eq!(bundle.actions.len(), 2);
+
+    bundle.check_policy(&Policy::default()).unwrap();
+    assert_eq!(
+        bundle.check_policy(&Policy {
+            min_actions: 4,
+            ..Policy::default()
+        }),
+        Err(PolicyError::TooFewActions)
+    );
 }
 
 #[test]
@@ -1071,12 +1692,151 @@ fn invalid_action_sig_fails_verification() {
     bundle.actions[0].sig = bad_sig;
 
     let err = bundle.verify_signatures(&sighash).unwrap_err();
-    let SignatureError::Action(sig) = err else {
+    let SignatureError::Action(index, sig) = err else {
         panic!("expected SignatureError::Action, got {err:?}");
     };
+    assert_eq!(index, 0);
     assert_eq!(sig, bad_sig);
 }
 
+/// A signature batch accepts valid bundles and names the bundle and
+/// signature at fault when one is invalid.
+#[test]
+fn signature_batch_identifies_invalid_signature() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let first = build_autonome(rng, &wallet, 1000, 700);
+    let mut second = build_autonome(rng, &wallet, 500, 400);
+    let first_sighash = mock_sighash(first.commitment());
+    let second_sighash = mock_sighash(second.commitment());
+
+    let mut batch = SignatureBatch::new();
+    assert_eq!(batch.add(&first, &first_sighash), 0);
+    assert_eq!(batch.add(&second, &second_sighash), 1);
+    batch.clone().verify(rng).unwrap();
+
+    // well-formed, but made over another sighash
+    let stolen = first.actions[1].sig;
+    second.actions[1].sig = stolen;
+    let mut batch = SignatureBatch::new();
+    batch.add(&first, &first_sighash);
+    batch.add(&second, &second_sighash);
+    let err = batch.verify(rng).unwrap_err();
+    assert_eq!(err.bundle, 1);
+    let SignatureError::Action(index, sig) = err.error else {
+        panic!("expected SignatureError::Action, got {err:?}");
+    };
+    assert_eq!(index, 1);
+    assert_eq!(sig, stolen);
+}
+
+/// A verified bundle caches the commitment and binding key it verified with,
+/// and can be shared between threads.
+#[test]
+fn verified_bundle_caches_derived_values() {
+    const fn shareable<T: Send + Sync>(_verified: &T) {}
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let bundle = build_autonome(rng, &wallet, 1000, 700);
+    let sighash = mock_sighash(bundle.commitment());
+
+    let verified = VerifiedBundle::new(bundle.clone(), &sighash).unwrap();
+    shareable(&verified);
+    assert_eq!(verified.commitment(), bundle.commitment());
+    assert_eq!(
+        verified.bvk(),
+        public::BindingVerificationKey::derive(&bundle.actions, bundle.value_balance)
+    );
+    assert_eq!(verified.into_bundle(), bundle);
+
+    let err = VerifiedBundle::new(bundle, &mock_sighash([0u8; 32])).unwrap_err();
+    assert!(
+        matches!(err, SignatureError::Binding(_)),
+        "expected a binding signature error, got {err:?}"
+    );
+}
+
+/// A verifier checks anchors on queueing and defers signatures and proofs to
+/// finalization.
+#[test]
+fn verifier_defers_expensive_checks() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let bundle = build_autonome(rng, &wallet, 1000, 700);
+    let sighash = mock_sighash(bundle.commitment());
+    let wtxid: [u8; 64] = mock_wtxid(&bundle).into();
+    let anchor = bundle.stamp.anchor;
+    let epoch_of = |candidate: &Anchor| (*candidate == anchor).then_some(EpochIndex(0));
+    let policy = AnchorPolicy {
+        current_epoch: EpochIndex(1),
+        window: 1,
+        epoch_of: &epoch_of,
+    };
+    let expired = AnchorPolicy {
+        current_epoch: EpochIndex(2),
+        ..policy
+    };
+
+    let mut verifier = Verifier::new();
+    assert!(
+        matches!(
+            verifier.queue_stamp(bundle.clone(), &wtxid, vec![], &expired),
+            Err(VerifierError::Stamp(0, VerificationError::AnchorOutOfRange))
+        ),
+        "an anchor outside the window is rejected on queueing"
+    );
+    assert_eq!(verifier.queue_signatures(&bundle, &sighash), 0);
+    assert_eq!(
+        verifier
+            .queue_stamp(bundle.clone(), &wtxid, vec![], &policy)
+            .unwrap(),
+        0
+    );
+    verifier.clone().finalize(rng).unwrap();
+
+    verifier.queue_signatures(&bundle, &mock_sighash([0u8; 32]));
+    let err = verifier.finalize(rng).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            VerifierError::Signature(BatchSignatureError { bundle: 1, .. })
+        ),
+        "expected a signature error in bundle 1, got {err:?}"
+    );
+}
+
+/// A proof queue passes valid stamps and names the first invalid one.
+#[test]
+fn proof_queue_names_the_failing_stamp() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let anchor = PoolSim::genesis(rng).anchor();
+    let first = build_output_bundle(rng, &wallet, anchor, 300);
+    let second = build_output_bundle(rng, &wallet, anchor, 200);
+
+    let mut queue = ProofQueue::new();
+    assert_eq!(
+        queue
+            .add(first.stamp.clone(), first.actions.clone())
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        queue
+            .add(second.stamp.clone(), second.actions.clone())
+            .unwrap(),
+        1
+    );
+    queue.clone().verify(rng).expect("valid proofs");
+
+    queue
+        .add(second.stamp.clone(), first.actions.clone())
+        .unwrap();
+    let err = queue.verify(rng).expect_err("mismatched actions");
+    assert_eq!(err.stamp, 2);
+}
+
 #[test]
 fn stamped_read_write_round_trip() {
     let rng = &mut StdRng::seed_from_u64(0);
@@ -1084,6 +1844,7 @@ fn stamped_read_write_round_trip() {
     let original = build_autonome(rng, &wallet, 1000, 700);
     let mut buf = Vec::new();
     original.write(&mut buf).expect("write");
+    assert_eq!(original.weight(), buf.len(), "weight is the encoded size");
     let deserialized = Bundle::<ProofStamp>::read(&*buf).expect("read");
 
     assert_eq!(original.actions, deserialized.actions);
@@ -1171,6 +1932,7 @@ fn stripped_read_write_round_trip() {
 
     let mut buf = Vec::new();
     stripped.write(&mut buf).expect("write");
+    assert_eq!(stripped.weight(), buf.len(), "weight is the encoded size");
     let deserialized = Bundle::<PointerStamp>::read(&*buf).expect("read");
 
     assert_eq!(stripped.commitment(), deserialized.commitment());
@@ -1239,7 +2001,9 @@ fn tachyon_bundle_wire_round_trip() {
         let decoded = TachyonBundle::read(&*buf).expect("read");
         let back = Bundle::<ProofStamp>::try_from(decoded).expect("stamped variant");
 
-        // Stamp carries a proof and is not PartialEq, so compare fields.
+        // Stamps compare by digest; the fields are checked too, for clearer
+        // failures.
+        assert_eq!(stamped.stamp, back.stamp);
         assert_eq!(stamped.actions, back.actions);
         assert_eq!(stamped.value_balance, back.value_balance);
         assert_eq!(stamped.stamp.tachygrams, back.stamp.tachygrams);
@@ -1261,6 +2025,63 @@ fn tachyon_bundle_wire_round_trip() {
     }
 }
 
+/// A stripped bundle checks what it can of its aggregate alone, and keeps
+/// its signatures across both transitions.
+#[test]
+fn stripped_bundle_requires_its_aggregate() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let anchor = PoolSim::genesis(rng).anchor();
+    let autonome = build_output_bundle(rng, &wallet, anchor, 300);
+    let sighash = mock_sighash(autonome.commitment());
+    let own_stamp = autonome.stamp.clone();
+
+    let aggregate = Aggregate::new(build_output_bundle(rng, &wallet, anchor, 400), vec![])
+        .merge(rng, [autonome], mock_wtxid)
+        .expect("merge autonome");
+    let wtxid: [u8; 64] = mock_wtxid(aggregate.bundle()).into();
+    let (covering, adjuncts) = aggregate.into_parts();
+    let adjunct = adjuncts.into_iter().next().expect("one adjunct");
+
+    assert_eq!(adjunct.requires(&covering, &wtxid), Ok(()));
+    adjunct
+        .verify_signatures(&sighash)
+        .expect("stripping keeps signatures");
+
+    let lone = build_output_bundle(rng, &wallet, anchor, 100);
+    let lone_wtxid: [u8; 64] = mock_wtxid(&lone).into();
+    assert_eq!(
+        adjunct.requires(&covering, &lone_wtxid),
+        Err(TransitionError::PointerMismatch)
+    );
+
+    let pointed = adjunct
+        .clone()
+        .restamp(own_stamp.clone())
+        .strip(mock_wtxid(&lone));
+    assert_eq!(
+        pointed.requires(&lone, &lone_wtxid),
+        Err(TransitionError::NotAggregate)
+    );
+
+    let repeated = covering
+        .clone()
+        .strip(PointerStamp::try_from(wtxid).expect("nonzero"));
+    assert_eq!(
+        repeated.requires(&covering, &wtxid),
+        Err(TransitionError::SharedAction)
+    );
+
+    let restamped = adjunct.restamp(own_stamp);
+    assert!(restamped.is_autonome(), "its own stamp covers it again");
+    restamped
+        .verify_signatures(&sighash)
+        .expect("restamping keeps signatures");
+
+    assert!(Transition::Strip.rechecks().contains(&Recheck::Pointer));
+    assert!(!Transition::Restamp.rechecks().contains(&Recheck::Pointer));
+}
+
 #[test]
 fn aggregate_id_try_from_rejects_zero() {
     PointerStamp::try_from([0u8; 64]).unwrap_err();
@@ -1558,13 +2379,14 @@ fn coverage_check_matches_stamp_actions() {
 
     let innocent_descriptors: BTreeSet<action::Descriptor> =
         descriptors_a.union(&descriptors_b).copied().collect();
-    let innocent_stamp = ProofStamp::merge(rng, (stamp_a, descriptors_a), (stamp_b, descriptors_b))
-        .expect("innocent merge");
+    let innocent_stamp =
+        ProofStamp::merge(rng, (stamp_a, &descriptors_a), (stamp_b, &descriptors_b))
+            .expect("innocent merge");
 
     let based_stamp = ProofStamp::merge(
         rng,
-        (becomes_based.stamp, based_descriptors),
-        (innocent_stamp, innocent_descriptors),
+        (becomes_based.stamp, &based_descriptors),
+        (innocent_stamp, &innocent_descriptors),
     )
     .expect("based merge");
     becomes_based.stamp = based_stamp;
@@ -1592,6 +2414,22 @@ fn coverage_check_matches_stamp_actions() {
     );
 }
 
+/// A binding signature whose `s` is not a canonical scalar is rejected on
+/// read, so the bundle has a single accepted encoding.
+#[test]
+fn read_rejects_noncanonical_binding_sig() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let bundle = build_autonome(rng, &wallet, 1000, 700);
+
+    let mut sig = Vec::new();
+    bundle.binding_sig.write(&mut sig).unwrap();
+    sig[32..].copy_from_slice(&[0xFF; 32]);
+
+    let err = Signature::read(sig.as_slice()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
 /// A stamp whose tachygrams are not in canonical order is rejected on read,
 /// matching the order the stamp digest commits to.
 #[test]
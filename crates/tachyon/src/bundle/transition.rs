@@ -0,0 +1,67 @@
+//! Bundle state transitions, as a mempool sees them.
+//!
+//! A bundle's signatures cover the transaction sighash, which commits to its
+//! actions and value balance but not to its stamp. Stripping a stamped bundle,
+//! or giving a stripped one back a stamp of its own, leaves every signature
+//! valid: only the stamp covering the actions moves.
+
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+
+/// Errors from checking a stripped bundle against the aggregate meant to
+/// cover it.
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "intentional name")]
+pub enum TransitionError {
+    /// The pointer names another transaction.
+    #[display("pointer does not name the aggregate's transaction")]
+    PointerMismatch,
+    /// The covering bundle's stamp covers only its own actions.
+    #[display("covering bundle is not an aggregate")]
+    NotAggregate,
+    /// The stripped bundle repeats an action of the covering bundle.
+    #[display("action shared with the covering bundle")]
+    SharedAction,
+}
+
+/// A change of state a bundle in the mempool may undergo.
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub enum Transition {
+    /// A stamped autonome is stripped to an adjunct of an aggregate.
+    Strip,
+    /// An adjunct is given back a stamp of its own, as when its aggregate is
+    /// evicted or falls out of the chain.
+    Restamp,
+}
+
+/// A check to run again after a [`Transition`].
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub enum Recheck {
+    /// The pointer names the covering aggregate's wtxid.
+    Pointer,
+    /// The covering stamp covers exactly the actions it should, each once.
+    Coverage,
+    /// The covering stamp's proof verifies.
+    Proof,
+    /// The covering stamp's anchor is accepted by the landing block.
+    Anchor,
+}
+
+impl Transition {
+    /// The checks a bundle must pass again after this transition.
+    ///
+    /// Signatures never appear: they do not cover the stamp, so a bundle
+    /// whose signatures verified before the transition keeps them valid.
+    #[must_use]
+    pub const fn rechecks(self) -> &'static [Recheck] {
+        match self {
+            Self::Strip => &[
+                Recheck::Pointer,
+                Recheck::Coverage,
+                Recheck::Proof,
+                Recheck::Anchor,
+            ],
+            Self::Restamp => &[Recheck::Coverage, Recheck::Proof, Recheck::Anchor],
+        }
+    }
+}
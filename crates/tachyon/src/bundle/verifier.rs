@@ -0,0 +1,530 @@
+//! Deferred verification of many bundles.
+
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::mem;
+
+use derive_more::{Debug, Display, Error};
+use rand_core::{CryptoRng, RngCore};
+
+use super::{
+    Bundle, BundleState, SignatureError, VerificationError, VerifyCoverageError,
+    VerifyPointersError, VerifyProofError,
+};
+use crate::{
+    action::{self, Action},
+    keys::public,
+    primitives::{ActionDigest, ActionSetAccumulator, AnchorPolicy},
+    reddsa,
+    stamp::{PointerStamp, ProofStamp, StampState},
+};
+
+/// A signature failure found by [`SignatureBatch::verify`].
+#[derive(Clone, Copy, Debug, Display, Error)]
+#[display("bundle {bundle}: {error}")]
+pub struct BatchSignatureError {
+    /// Index of the offending bundle, as returned by [`SignatureBatch::add`].
+    pub bundle: usize,
+    /// The offending signature.
+    #[error(source)]
+    pub error: SignatureError,
+}
+
+impl BatchSignatureError {
+    /// Attribute the failure within a batch holding an aggregate and then its
+    /// adjuncts, in order.
+    pub(super) const fn attribute(self) -> VerificationError {
+        match self.bundle.checked_sub(1) {
+            None => VerificationError::Signature(self.error),
+            Some(index) => VerificationError::AdjunctSignature(index, self.error),
+        }
+    }
+}
+
+/// Accumulates the action and binding signatures of any number of bundles,
+/// to check them in a single multiscalar multiplication.
+///
+/// Equivalent to calling [`Bundle::verify_signatures`] on each bundle, but
+/// much cheaper for a block or a mempool backlog. A failed batch does not say
+/// which signature is invalid, so [`verify`](Self::verify) then falls back to
+/// checking each signature singly.
+#[derive(Clone, Debug, Default)]
+pub struct SignatureBatch {
+    bundles: usize,
+    items: Vec<(usize, SignatureError, reddsa::BatchItem)>,
+}
+
+impl SignatureBatch {
+    /// Start an empty batch.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bundles: 0,
+            items: Vec::new(),
+        }
+    }
+
+    /// Number of signatures queued.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no signature is queued.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Queue a bundle's signatures over `sighash`, returning its index in the
+    /// batch.
+    pub fn add<S: BundleState + ?Sized>(
+        &mut self,
+        bundle: &Bundle<S>,
+        sighash: &[u8; 32],
+    ) -> usize {
+        let index = self.bundles;
+        self.bundles += 1;
+
+        let bvk = public::BindingVerificationKey::derive(&bundle.actions, bundle.value_balance);
+        self.items.push((
+            index,
+            SignatureError::Binding(bundle.binding_sig),
+            bvk.batch_item(sighash, &bundle.binding_sig),
+        ));
+        for (position, action) in bundle.actions.iter().enumerate() {
+            self.items.push((
+                index,
+                SignatureError::Action(position, action.sig),
+                action.rk.batch_item(sighash, &action.sig),
+            ));
+        }
+
+        index
+    }
+
+    /// Verify every queued signature.
+    ///
+    /// If the batch fails, reports the first invalid signature in queue
+    /// order.
+    pub fn verify<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+    ) -> Result<(), BatchSignatureError> {
+        let mut verifier = reddsa::BatchVerifier::new();
+        for entry in &self.items {
+            verifier.queue(entry.2.clone());
+        }
+        verifier.verify(&mut *rng).map_err(|_err| self.blame())
+    }
+
+    /// Find the signature a failed batch is to blame on.
+    ///
+    /// Valid signatures always satisfy the batch equation, so some signature
+    /// should fail singly. If none does, the batch is still rejected, blamed
+    /// on the first bundle queued.
+    fn blame(self) -> BatchSignatureError {
+        let first = self.items.first().map_or(0, |entry| entry.0);
+        self.items
+            .into_iter()
+            .find(|entry| entry.2.verify_single().is_err())
+            .map_or(
+                BatchSignatureError {
+                    bundle: first,
+                    error: SignatureError::Batch,
+                },
+                |entry| BatchSignatureError {
+                    bundle: entry.0,
+                    error: entry.1,
+                },
+            )
+    }
+}
+
+/// A proof found invalid by [`ProofQueue::verify`].
+#[derive(Debug, Display, Error)]
+#[display("stamp {stamp}: {error}")]
+pub struct QueuedProofError {
+    /// Index of the offending stamp, as returned by [`ProofQueue::add`].
+    pub stamp: usize,
+    /// Why its proof failed.
+    #[error(source)]
+    pub error: VerificationError,
+}
+
+/// Collects stamps, each with the actions it covers, to check their proofs
+/// later and name the one that fails.
+///
+/// This is not batch verification: [`verify`](Self::verify) checks each
+/// proof on its own, in the order added, stopping at the first that fails,
+/// so it costs as much as verifying the stamps one by one. What it saves is
+/// the bookkeeping: the proof header of each stamp is rebuilt from its
+/// actions when it is added, so an action that cannot be digested is
+/// reported at once, and a failure carries the index of its stamp.
+#[derive(Clone, Debug, Default)]
+pub struct ProofQueue {
+    #[debug(skip)]
+    items: Vec<(ProofStamp, Vec<ActionDigest>)>,
+}
+
+impl ProofQueue {
+    /// Start an empty queue.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Number of stamps queued.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no stamp is queued.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Queue a stamp and the actions it covers, in wire order, returning its
+    /// index in the queue.
+    ///
+    /// Coverage is not checked here: see [`Bundle::verify_coverage`].
+    pub fn add(
+        &mut self,
+        stamp: ProofStamp,
+        actions: impl IntoIterator<Item = Action>,
+    ) -> Result<usize, VerifyProofError> {
+        let digests = actions
+            .into_iter()
+            .enumerate()
+            .map(|(index, action)| {
+                action
+                    .digest()
+                    .map_err(|err| VerifyProofError::ActionDigest(index, err))
+            })
+            .collect::<Result<Vec<ActionDigest>, VerifyProofError>>()?;
+        self.items.push((stamp, digests));
+        Ok(self.items.len() - 1)
+    }
+
+    /// Verify every queued proof in turn, reporting the first that fails.
+    pub fn verify<RNG: RngCore + CryptoRng>(self, rng: &mut RNG) -> Result<(), QueuedProofError> {
+        for (stamp, (proof, digests)) in self.items.into_iter().enumerate() {
+            match proof.verify_proof(rng, digests) {
+                Ok(true) => {},
+                Ok(false) => {
+                    return Err(QueuedProofError {
+                        stamp,
+                        error: VerificationError::Disproved,
+                    });
+                },
+                Err(err) => {
+                    return Err(QueuedProofError {
+                        stamp,
+                        error: VerificationError::Proof(VerifyProofError::ProofSystem(err)),
+                    });
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Errors from a [`Verifier`].
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum VerifierError {
+    /// A queued signature is invalid.
+    #[display("{_0}")]
+    Signature(BatchSignatureError),
+    /// A queued stamp failed verification.
+    #[display("stamp {_0}: {_1}")]
+    Stamp(usize, #[error(source)] VerificationError),
+}
+
+/// Accumulates bundles for verification in one deferred pass.
+///
+/// A node queues each transaction's bundles as it arrives. Queueing runs the
+/// cheap checks, the stamp's anchor, adjunct pointers, and coverage, at once,
+/// so a mempool can reject malformed bundles immediately.
+/// [`finalize`](Self::finalize) then runs the expensive checks together: all
+/// signatures in a single [`SignatureBatch`], then every proof queued in a
+/// [`ProofQueue`].
+#[derive(Clone, Debug, Default)]
+pub struct Verifier {
+    signatures: SignatureBatch,
+    proofs: ProofQueue,
+}
+
+impl Verifier {
+    /// Start an empty verifier.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            signatures: SignatureBatch::new(),
+            proofs: ProofQueue::new(),
+        }
+    }
+
+    /// Queue a bundle's signatures over `sighash`, returning its index in the
+    /// signature batch.
+    pub fn queue_signatures<S: BundleState + ?Sized>(
+        &mut self,
+        bundle: &Bundle<S>,
+        sighash: &[u8; 32],
+    ) -> usize {
+        self.signatures.add(bundle, sighash)
+    }
+
+    /// Queue a proof stamp and the adjuncts it covers, returning the stamp's
+    /// index.
+    ///
+    /// Checks now that the stamp's anchor is one `anchor_policy` accepts, as
+    /// [`Bundle::verify_for_block`] does, that every adjunct points to
+    /// `wtxid`, and that the stamp covers exactly these actions.
+    /// Its proof header is rebuilt now, and the proof checked by
+    /// [`finalize`](Self::finalize). Signatures are queued separately, with
+    /// [`queue_signatures`](Self::queue_signatures).
+    pub fn queue_stamp(
+        &mut self,
+        aggregate: Bundle<ProofStamp>,
+        wtxid: &[u8; 64],
+        adjuncts: Vec<Bundle<PointerStamp>>,
+        anchor_policy: &AnchorPolicy<'_>,
+    ) -> Result<usize, VerifierError> {
+        let index = self.proofs.len();
+        if !aggregate.stamp.anchor.is_valid_for(anchor_policy) {
+            return Err(VerifierError::Stamp(
+                index,
+                VerificationError::AnchorOutOfRange,
+            ));
+        }
+
+        let adjuncts_dyn: Vec<&Bundle<dyn StampState>> =
+            adjuncts.iter().map(Bundle::as_dyn).collect();
+        aggregate
+            .verify_pointers(wtxid, &adjuncts_dyn)
+            .map_err(|err| VerifierError::Stamp(index, VerificationError::Pointers(err)))?;
+        aggregate
+            .verify_coverage(&adjuncts_dyn)
+            .map_err(|err| VerifierError::Stamp(index, VerificationError::Coverage(err)))?;
+
+        let actions = aggregate
+            .actions
+            .into_iter()
+            .chain(adjuncts.into_iter().flat_map(|adjunct| adjunct.actions));
+        self.proofs
+            .add(aggregate.stamp, actions)
+            .map_err(|err| VerifierError::Stamp(index, VerificationError::Proof(err)))
+    }
+
+    /// Verify every queued signature and proof.
+    ///
+    /// Signatures are checked first, then proofs in queue order; the first
+    /// failure is returned.
+    pub fn finalize<RNG: RngCore + CryptoRng>(self, rng: &mut RNG) -> Result<(), VerifierError> {
+        self.signatures
+            .verify(rng)
+            .map_err(VerifierError::Signature)?;
+
+        self.proofs
+            .verify(rng)
+            .map_err(|err| VerifierError::Stamp(err.stamp, err.error))
+    }
+}
+
+/// A bundle whose signatures have been verified, with the values derived from
+/// its public data cached.
+///
+/// Mempool revalidation, txid computation, and block assembly each need the
+/// bundle commitment and the binding verification key. A `VerifiedBundle`
+/// computes both once, when verified, rather than repeating the BLAKE2b
+/// passes and point summations; both are cheap next to the verification
+/// itself. Nothing is computed lazily, so a verified bundle can be shared
+/// between threads.
+#[derive(Clone, Debug)]
+pub struct VerifiedBundle<S: BundleState> {
+    bundle: Bundle<S>,
+    commitment: [u8; 32],
+    bvk: public::BindingVerificationKey,
+}
+
+impl<S: BundleState> VerifiedBundle<S> {
+    /// Verify the bundle's signatures over `sighash`.
+    pub fn new(bundle: Bundle<S>, sighash: &[u8; 32]) -> Result<Self, SignatureError> {
+        let bvk = public::BindingVerificationKey::derive(&bundle.actions, bundle.value_balance);
+        bundle.verify_signatures_with(sighash, &bvk)?;
+        Ok(Self {
+            commitment: bundle.commitment(),
+            bundle,
+            bvk,
+        })
+    }
+
+    /// The verified bundle.
+    #[must_use]
+    pub const fn bundle(&self) -> &Bundle<S> {
+        &self.bundle
+    }
+
+    /// Unwrap the verified bundle, discarding the cache.
+    #[must_use]
+    pub fn into_bundle(self) -> Bundle<S> {
+        self.bundle
+    }
+
+    /// The bundle commitment. See [`Bundle::commitment`].
+    #[must_use]
+    pub const fn commitment(&self) -> [u8; 32] {
+        self.commitment
+    }
+
+    /// The binding verification key derived from the bundle's actions and
+    /// value balance.
+    #[must_use]
+    pub const fn bvk(&self) -> public::BindingVerificationKey {
+        self.bvk
+    }
+}
+
+/// Signatures a [`StreamingVerifier`] queues before checking the batch and
+/// dropping it.
+const STREAMED_SIGNATURES: usize = 1024;
+
+/// Verifies an aggregate against adjuncts supplied one at a time.
+///
+/// [`Bundle::verify_for_block`] holds every adjunct at once. For a
+/// block-sized aggregate this streaming form keeps only what the final checks
+/// need from each action, its descriptor and its root in the stamp's action
+/// set, so each adjunct can be dropped, or never fully held, once pushed.
+/// The anchor is checked when verification starts. Pointer and
+/// duplicate-action checks run as adjuncts arrive, and signatures are checked
+/// in batches of a bounded size as they fill; [`finish`](Self::finish) checks
+/// coverage, the last signatures, and the proof.
+#[derive(Debug)]
+#[expect(clippy::module_name_repetitions, reason = "intentional name")]
+pub struct StreamingVerifier<'stamp> {
+    stamp: &'stamp ProofStamp,
+    wtxid: [u8; 64],
+    signatures: SignatureBatch,
+    checked_bundles: usize,
+    descriptors: BTreeSet<action::Descriptor>,
+    #[debug(skip)]
+    action_set: ActionSetAccumulator,
+}
+
+impl<'stamp> StreamingVerifier<'stamp> {
+    /// Start verifying `aggregate`, carried by a transaction with `sighash`
+    /// and `wtxid`, in a block whose [`AnchorPolicy`] is `anchor_policy`.
+    pub fn new<RNG: RngCore + CryptoRng>(
+        rng: &mut RNG,
+        aggregate: &'stamp Bundle<ProofStamp>,
+        sighash: &[u8; 32],
+        wtxid: [u8; 64],
+        anchor_policy: &AnchorPolicy<'_>,
+    ) -> Result<Self, VerificationError> {
+        if !aggregate.stamp.anchor.is_valid_for(anchor_policy) {
+            return Err(VerificationError::AnchorOutOfRange);
+        }
+        PointerStamp::try_from(wtxid).map_err(|err| {
+            VerificationError::Pointers(VerifyPointersError::AdjunctPointerInvalid(err))
+        })?;
+
+        let mut stream = Self {
+            stamp: &aggregate.stamp,
+            wtxid,
+            signatures: SignatureBatch::new(),
+            checked_bundles: 0,
+            descriptors: BTreeSet::new(),
+            action_set: ActionSetAccumulator::new(),
+        };
+        stream.absorb(rng, aggregate, sighash)?;
+        Ok(stream)
+    }
+
+    /// Absorb the next adjunct, carried by a transaction with `sighash`.
+    ///
+    /// Once enough signatures are queued, they are checked here, and a bad
+    /// one is reported against the adjunct that carried it.
+    pub fn push_adjunct<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+        adjunct: &Bundle<PointerStamp>,
+        sighash: &[u8; 32],
+    ) -> Result<(), VerificationError> {
+        if adjunct.stamp.stamp_digest() != self.wtxid {
+            return Err(VerificationError::Pointers(
+                VerifyPointersError::AdjunctPointerMismatch,
+            ));
+        }
+        self.absorb(rng, adjunct, sighash)
+    }
+
+    fn absorb<RNG: RngCore + CryptoRng, S: BundleState + ?Sized>(
+        &mut self,
+        rng: &mut RNG,
+        bundle: &Bundle<S>,
+        sighash: &[u8; 32],
+    ) -> Result<(), VerificationError> {
+        self.signatures.add(bundle, sighash);
+        for action in &bundle.actions {
+            let index = self.descriptors.len();
+            let digest = action.digest().map_err(|err| {
+                VerificationError::Proof(VerifyProofError::ActionDigest(index, err))
+            })?;
+            if !self.descriptors.insert(action.descriptor()) {
+                return Err(VerificationError::Coverage(
+                    VerifyCoverageError::DuplicateActions,
+                ));
+            }
+            self.action_set.insert(digest);
+        }
+        if self.signatures.len() >= STREAMED_SIGNATURES {
+            self.check_signatures(rng)?;
+        }
+        Ok(())
+    }
+
+    /// Check the queued signatures and start a new batch.
+    fn check_signatures<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+    ) -> Result<(), VerificationError> {
+        let batch = mem::take(&mut self.signatures);
+        let bundles = batch.bundles;
+        batch.verify(rng).map_err(|err| {
+            BatchSignatureError {
+                bundle: self.checked_bundles + err.bundle,
+                error: err.error,
+            }
+            .attribute()
+        })?;
+        self.checked_bundles += bundles;
+        Ok(())
+    }
+
+    /// Check that the stamp covers exactly the absorbed actions, then verify
+    /// the remaining signatures and the proof.
+    pub fn finish<RNG: RngCore + CryptoRng>(
+        mut self,
+        rng: &mut RNG,
+    ) -> Result<(), VerificationError> {
+        if !self.stamp.is_covering(mem::take(&mut self.descriptors)) {
+            return Err(VerificationError::Coverage(
+                VerifyCoverageError::StampActionsMismatch,
+            ));
+        }
+
+        self.check_signatures(rng)?;
+
+        let verified = self
+            .stamp
+            .verify_proof_for(rng, &self.action_set.into())
+            .map_err(|err| VerificationError::Proof(VerifyProofError::ProofSystem(err)))?;
+        if verified {
+            Ok(())
+        } else {
+            Err(VerificationError::Disproved)
+        }
+    }
+}
@@ -1,8 +1,23 @@
 //! Protocol-wide non-hash constants.
 
+use ragu::proof::PROOF_SIZE_COMPRESSED;
+
 /// Maximum representable value in zatoshis (§5.3 of the protocol spec).
 pub const MAX_MONEY: u64 = 2_100_000_000_000_000;
 
+/// Maximum serialized block size in bytes, the Zcash consensus limit.
+pub const MAX_BLOCK_SIZE: usize = 2_000_000;
+
+#[expect(
+    clippy::integer_division,
+    clippy::integer_division_remainder_used,
+    reason = "const arithmetic"
+)]
+/// Most tachygrams a proof stamp may carry: as many 32-byte encodings as fit
+/// in a block beside the stamp's coverage digest, anchor, tachygram count,
+/// and proof. At this size the count's compactsize takes 5 bytes.
+pub const MAX_STAMP_TACHYGRAMS: usize = (MAX_BLOCK_SIZE - 32 - 32 - 5 - PROOF_SIZE_COMPRESSED) / 32;
+
 const EPOCH_SHIFT: u32 = if cfg!(test) { 4 } else { 12 };
 
 /// Number of blocks per epoch.
@@ -260,3 +260,58 @@ lazy_static! {
         hasher_256(AUTH_DIGEST_PERSONALIZATION, |_| {})
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// Decode a 64-digit hex string.
+    fn hex(digits: &str) -> [u8; 32] {
+        digits
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                u8::from_str_radix(core::str::from_utf8(pair).expect("ascii"), 16).expect("hex")
+            })
+            .collect::<Vec<u8>>()
+            .try_into()
+            .expect("32 bytes")
+    }
+
+    /// Fixed inputs to the stamp digests, and the BLAKE2b outputs expected of
+    /// them, for cross-checking other implementations.
+    #[test]
+    fn stamp_digest_vectors() {
+        let proof: Vec<u8> = (0..64).collect();
+        let proof_digest = stamp_proof_digest(&proof);
+        assert_eq!(
+            proof_digest,
+            hex("8ba0ac3474242b94f7b6bed731040488538779bf5ee673155e668af862d2611c")
+        );
+
+        let mut anchor = [0u8; 32];
+        anchor[0] = 1;
+        let mut tachygrams = [[0u8; 32]; 2];
+        tachygrams[0][0] = 2;
+        tachygrams[1][0] = 3;
+        assert_eq!(
+            stamp_data_digest(proof_digest, anchor, &tachygrams),
+            hex("1a5c139336651a8221c8f95d7c303fd7f99eff3d6d116af63c8c07bfdd337f2f")
+        );
+        assert_eq!(
+            stamp_data_digest(proof_digest, anchor, &[]),
+            hex("659fabf90e86cf380dbb14fab195d776ac0c6e95ffd481458a86f3603f79f338")
+        );
+
+        assert_eq!(
+            action_descriptor_digest(&[[1u8; 64], [2u8; 64]]),
+            hex("41c246d4a32ed694845c9910a11fffaa42a8bec3b14aebdccba1a728a8cef0a6")
+        );
+        assert_eq!(
+            action_descriptor_digest(&[]),
+            hex("bc6a47eed78ff2e9a5baa07d21400d517da232fea21ceb63d01f189265259488")
+        );
+    }
+}
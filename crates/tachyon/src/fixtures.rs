@@ -33,8 +33,8 @@ use crate::{
     keys::{NoteMasterKey, PaymentKey, ProofAuthorizingKey, private},
     note::{self, Note, Nullifier, NullifierTrapdoor},
     primitives::{
-        ActionSetPoly, Anchor, BlockHeight, EpochIndex, Tachygram, TachygramSetCommit,
-        TachygramSetPoly, effect,
+        ActionSetPoly, Anchor, BlockHeight, EpochIndex, Tachygram, TachygramDisjointness,
+        TachygramSetCommit, TachygramSetPoly, effect,
     },
     stamp::{
         PointerStamp, ProofStamp, StampState,
@@ -87,10 +87,10 @@ pub fn mock_auth_digest(bundle_auth_digest: [u8; 32]) -> [u8; 32] {
 /// A stand-in for the covering aggregate's `wtxid = txid || auth_digest`.
 ///
 /// Kept distinct from [`mock_sighash`] on purpose: the transaction sighash
-/// equals the txid only for a fully shielded transaction, and `Bundle::verify`
-/// takes the sighash and the covering wtxid as independent inputs. Deriving the
-/// txid half from a separate transform lets tests catch any regression that
-/// conflates the two.
+/// equals the txid only for a fully shielded transaction, and
+/// `Bundle::verify_for_block` takes the sighash and the covering wtxid as
+/// independent inputs. Deriving the txid half from a separate transform lets
+/// tests catch any regression that conflates the two.
 pub fn mock_wtxid<S: StampState + 'static>(bundle: &Bundle<S>) -> PointerStamp {
     let mut wtxid = [0u8; 64];
     wtxid[..32].copy_from_slice(&mock_txid(bundle.commitment()));
@@ -160,6 +160,23 @@ pub fn build_output_stamp(
     (stamp, plan)
 }
 
+/// A signed, proof-stamped bundle of a single output paying `value`.
+pub fn build_output_bundle(
+    rng: &mut (impl RngCore + CryptoRng),
+    wallet: &WalletSim,
+    anchor: Anchor,
+    value: u64,
+) -> Bundle<ProofStamp> {
+    let ask = wallet.sk.derive_auth_private();
+    let (stamp, plan) = build_output_stamp(rng, anchor, wallet.random_note(value));
+    let bundle_plan = bundle::Plan::new(vec![], vec![plan]);
+    let sighash = mock_sighash(bundle_plan.commitment().expect("fixture commitment"));
+    bundle_plan
+        .sign(rng, &sighash, &ask)
+        .expect("sign output bundle")
+        .stamp(stamp)
+}
+
 pub fn build_autonome(
     rng: &mut (impl RngCore + CryptoRng),
     wallet: &WalletSim,
@@ -185,17 +202,18 @@ pub fn build_autonome(
 
 /// An honest prover will not merge intersecting stamps.
 ///
-/// However, `MergeStamp` actually handles a commitment scheme that represents a
-/// multiset, and proves a relationship equivalent to a multiset union. So, a
-/// dishonest prover can feasibly prove a merge that violates consensus rules.
+/// The set commitments `MergeStamp` opens represent multisets, so its product
+/// relations alone would accept a multiset union. The step also proves the
+/// tachygram sets disjoint, which is what refuses an overlapping merge.
 ///
-/// Normal tools in this crate don't allow you to carry out such operations, so
-/// this utility will fuse a `MergeStamp` without checking for intersection.
+/// Normal tools in this crate refuse intersecting stamps before proving, so
+/// this utility fuses a `MergeStamp` over the multiset union, with the best
+/// disjointness witness there is, to exercise the step's own check.
 pub fn forge_overlapping_merge(
     rng: &mut (impl RngCore + CryptoRng),
     (stamp_a, descriptors_a): (&ProofStamp, &Vec<action::Descriptor>),
     (stamp_b, descriptors_b): (&ProofStamp, &Vec<action::Descriptor>),
-) -> Pcd<StampHeader> {
+) -> ragu::Result<Pcd<StampHeader>> {
     let left_acts = descriptors_a
         .iter()
         .map(|desc| desc.digest().expect("action digest"))
@@ -237,22 +255,25 @@ pub fn forge_overlapping_merge(
         .chain(stamp_b.tachygrams.iter())
         .copied()
         .collect::<TachygramSetPoly>();
+    let disjointness = TachygramDisjointness::new(
+        stamp_a.tachygrams.iter().copied(),
+        stamp_b.tachygrams.iter().copied(),
+    );
 
-    let (pcd, ()) = PROOF_SYSTEM
-        .fuse(
-            rng,
-            MergeStamp,
-            (
-                (left_acts, left_tg),
-                (merged_acts, merged_tg),
-                (right_acts, right_tg),
-            ),
-            left_pcd,
-            right_pcd,
-        )
-        .expect("multiset merge must prove");
+    let (pcd, ()) = PROOF_SYSTEM.fuse(
+        rng,
+        MergeStamp,
+        (
+            (left_acts, left_tg),
+            (merged_acts, merged_tg),
+            (right_acts, right_tg),
+            disjointness,
+        ),
+        left_pcd,
+        right_pcd,
+    )?;
 
-    pcd
+    Ok(pcd)
 }
 
 pub fn random_block(
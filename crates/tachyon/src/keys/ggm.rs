@@ -12,10 +12,13 @@
 use alloc::vec::Vec;
 use core::{num::NonZeroU8, ops::RangeInclusive};
 
+use corez::io::{self, Read, Write};
 use derive_more::{Debug, Eq as TotalEq, PartialEq};
 use pasta_curves::Fp;
 
-use crate::{constants::EPOCH_MAX, digest::poseidon, note::Nullifier, primitives::EpochIndex};
+use crate::{
+    constants::EPOCH_MAX, digest::poseidon, note::Nullifier, primitives::EpochIndex, serialization,
+};
 
 /// Maximum leaf index. Equal to [`EPOCH_MAX`] so every epoch maps to a
 /// distinct leaf.
@@ -211,6 +214,42 @@ impl NotePrefixedKey {
             self.inner, flavor.0, remaining,
         )))
     }
+
+    /// Read a prefix key `inner || depth || index`: the 32-byte node, the
+    /// depth as one byte, and the node index as a little-endian `u32`.
+    ///
+    /// Rejects a depth outside `1..=GGM_TREE_DEPTH`, and an index with no
+    /// node at that depth.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let inner = serialization::read_fp(&mut reader)?;
+        let mut depth_byte = [0u8; 1];
+        reader.read_exact(&mut depth_byte)?;
+        let depth = NonZeroU8::new(u8::from_le_bytes(depth_byte))
+            .filter(|nonzero| nonzero.get() <= GGM_TREE_DEPTH)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid GGM depth"))?;
+        let index = serialization::read_u32(&mut reader)?;
+        if index
+            .checked_shr(u32::from(depth.get() * GGM_CHUNK_SIZE))
+            .is_some_and(|above| above != 0)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "GGM index exceeds its depth",
+            ));
+        }
+        Ok(Self {
+            inner,
+            depth,
+            index,
+        })
+    }
+
+    /// Write a prefix key `inner || depth || index`.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_fp(&mut writer, &self.inner)?;
+        writer.write_all(&[self.depth.get()])?;
+        writer.write_all(&self.index.to_le_bytes())
+    }
 }
 
 /// Candidate starts for a cover of `[start..=end]`, rounded down to
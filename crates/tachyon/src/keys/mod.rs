@@ -98,6 +98,16 @@ mod tests {
         value,
     };
 
+    /// ak is sign-normalized whichever sign the PRF output gives it.
+    #[test]
+    fn ak_is_sign_normalized() {
+        for byte in 0u8..16 {
+            let sk = private::SpendingKey::from([byte; 32]);
+            let ak: [u8; 32] = sk.derive_auth_private().derive_auth_public().0.into();
+            assert_eq!(ak[31] >> 7u8, 0, "tilde_y of ak must be 0");
+        }
+    }
+
     /// ask, nk, pk derived from the same sk must all be different.
     /// pk derives from (ak, nk) via Poseidon, not directly from sk.
     #[test]
@@ -88,9 +88,10 @@ impl SpendingKey {
                 .expect("PRF-derived ask should be a valid RedPallas scalar"),
         )
         .into();
-        if ak[31] >> 7u8 == 1u8 {
-            ask = -ask;
-        }
+        // Negate by multiplying by 1 - 2 * tilde_y, without branching on the
+        // secret-derived bit.
+        let tilde_y = u64::from(ak[31] >> 7u8);
+        ask *= Fq::ONE - Fq::from(2 * tilde_y);
 
         // Build the final key from the sign-normalized scalar.
         SpendAuthorizingKey(
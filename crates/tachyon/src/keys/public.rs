@@ -44,6 +44,15 @@ impl ActionVerificationKey {
     pub fn verify(&self, sighash: &[u8; 32], sig: &action::Signature) -> Result<(), reddsa::Error> {
         self.0.verify(sighash, &sig.0)
     }
+
+    /// Queue an action signature for batch verification.
+    pub(crate) fn batch_item(
+        &self,
+        sighash: &[u8; 32],
+        sig: &action::Signature,
+    ) -> reddsa::BatchItem {
+        reddsa::BatchItem::from_spendauth(self.0.into(), sig.0, sighash)
+    }
 }
 
 impl TryFrom<EpAffine> for ActionVerificationKey {
@@ -121,6 +130,15 @@ impl BindingVerificationKey {
     pub fn verify(&self, sighash: &[u8; 32], sig: &bundle::Signature) -> Result<(), reddsa::Error> {
         self.0.verify(sighash, &sig.0)
     }
+
+    /// Queue a binding signature for batch verification.
+    pub(crate) fn batch_item(
+        &self,
+        sighash: &[u8; 32],
+        sig: &bundle::Signature,
+    ) -> reddsa::BatchItem {
+        reddsa::BatchItem::from_binding(self.0.into(), sig.0, sighash)
+    }
 }
 
 impl From<EpAffine> for BindingVerificationKey {
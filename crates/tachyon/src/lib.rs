@@ -12,11 +12,14 @@ extern crate std;
 extern crate alloc;
 
 pub mod action;
+pub mod aggregate;
+pub mod block;
 pub mod bundle;
 pub mod constants;
 pub mod entropy;
 pub mod keys;
 pub mod note;
+pub mod oss;
 pub mod reddsa;
 pub mod stamp;
 pub mod value;
@@ -31,9 +34,11 @@ mod serialization;
 pub(crate) mod fixtures;
 
 pub use action::{Action, Plan as ActionPlan};
+pub use aggregate::Aggregate;
+pub use block::TachyonBlockData;
 pub use bundle::{
     Bundle, Plan as BundlePlan, SignatureError, TachyonBundle, VerificationError,
-    VerifyCoverageError, VerifyPointersError, VerifyProofError,
+    VerifyCoverageError, VerifyParams, VerifyPointersError, VerifyProofError,
 };
 pub use note::Note;
 pub use primitives::*;
@@ -34,6 +34,9 @@
 //! (e.g. Sinsemilla, Poseidon) depends on what is efficient inside
 //! Ragu circuits and is TBD.
 
+use core::{fmt, str::FromStr};
+
+use corez::io::{self, Read, Write};
 use derive_more::{Debug, Eq as TotalEq, From, Into, PartialEq};
 use ff::Field as _;
 use pasta_curves::Fp;
@@ -42,8 +45,8 @@ use rand_core::{CryptoRng, RngCore};
 use crate::{
     digest::poseidon,
     keys::{NullifierKey, PaymentKey},
-    primitives::{EpochIndex, Tachygram},
-    value,
+    primitives::{EpochIndex, ParseHexError, Tachygram},
+    serialization, value,
 };
 
 /// Nullifier trapdoor ($\psi$) — per-note randomness for nullifier derivation.
@@ -131,6 +134,44 @@ impl Note {
         let mk = nk.derive_note_private(&self.psi);
         mk.derive_nullifier(flavor)
     }
+
+    /// Read a note `pk || value || psi || rcm`, with the value as a
+    /// little-endian u64.
+    ///
+    /// This is not a consensus encoding: a note is secret, and is only
+    /// exchanged between parties cooperating on a transaction.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let pk = PaymentKey(serialization::read_fp(&mut reader)?);
+        let value = {
+            let mut bytes = [0u8; size_of::<u64>()];
+            reader.read_exact(&mut bytes)?;
+            value::Positive::try_from(u64::from_le_bytes(bytes)).map_err(|_err| {
+                io::Error::new(io::ErrorKind::InvalidData, "note value out of range")
+            })
+        }?;
+        let psi = NullifierTrapdoor(serialization::read_fp(&mut reader)?);
+        let rcm = serialization::read_fp(&mut reader)?;
+        if rcm == Fp::ZERO {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "zero note commitment trapdoor",
+            ));
+        }
+        Ok(Self {
+            pk,
+            value,
+            psi,
+            rcm: CommitmentTrapdoor(rcm),
+        })
+    }
+
+    /// Write a note `pk || value || psi || rcm`. See [`read`](Self::read).
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_fp(&mut writer, &self.pk.0)?;
+        writer.write_all(&u64::from(self.value).to_le_bytes())?;
+        serialization::write_fp(&mut writer, &self.psi.0)?;
+        serialization::write_fp(&mut writer, &self.rcm.0)
+    }
 }
 
 /// A Tachyon note commitment (`cm`).
@@ -142,6 +183,36 @@ impl Note {
 #[derive(Clone, Copy, Debug, From, Into, PartialEq, TotalEq)]
 pub struct Commitment(#[debug(skip)] Fp);
 
+impl Commitment {
+    /// Read a 32-byte note commitment.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        serialization::read_fp(&mut reader).map(Self)
+    }
+
+    /// Write a 32-byte note commitment.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_fp(&mut writer, &self.0)
+    }
+}
+
+impl fmt::LowerHex for Commitment {
+    /// The 32-byte wire encoding, as for RPC and explorers.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialization::fmt_fp_hex(&self.0, formatter)
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = ParseHexError;
+
+    /// Parse the hex [`LowerHex`](fmt::LowerHex) prints.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        serialization::parse_fp_hex(hex)
+            .map(Self)
+            .ok_or(ParseHexError)
+    }
+}
+
 impl From<Commitment> for Tachygram {
     fn from(commitment: Commitment) -> Self {
         Self::from(commitment.0)
@@ -161,6 +232,36 @@ impl From<Commitment> for Tachygram {
 #[derive(Clone, Copy, Debug, From, Into, PartialEq, TotalEq)]
 pub struct Nullifier(#[debug(skip)] Fp);
 
+impl Nullifier {
+    /// Read a 32-byte nullifier.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        serialization::read_fp(&mut reader).map(Self)
+    }
+
+    /// Write a 32-byte nullifier.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_fp(&mut writer, &self.0)
+    }
+}
+
+impl fmt::LowerHex for Nullifier {
+    /// The 32-byte wire encoding, as for RPC and explorers.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialization::fmt_fp_hex(&self.0, formatter)
+    }
+}
+
+impl FromStr for Nullifier {
+    type Err = ParseHexError;
+
+    /// Parse the hex [`LowerHex`](fmt::LowerHex) prints.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        serialization::parse_fp_hex(hex)
+            .map(Self)
+            .ok_or(ParseHexError)
+    }
+}
+
 impl From<Nullifier> for Tachygram {
     fn from(nullifier: Nullifier) -> Self {
         Self::from(nullifier.0)
@@ -237,6 +338,29 @@ mod tests {
         assert_eq!(note.nullifier(&nk, flavor), mk.derive_nullifier(flavor));
     }
 
+    /// Nullifiers and commitments round-trip through bytes and hex.
+    #[test]
+    fn nullifier_and_commitment_encodings_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let nf = Nullifier::from(Fp::random(&mut *rng));
+        let cm = Commitment::from(Fp::random(&mut *rng));
+
+        let mut wire = alloc::vec::Vec::new();
+        nf.write(&mut wire).unwrap();
+        cm.write(&mut wire).unwrap();
+        let mut reader = wire.as_slice();
+        assert_eq!(Nullifier::read(&mut reader).unwrap(), nf);
+        assert_eq!(Commitment::read(&mut reader).unwrap(), cm);
+
+        assert_eq!(alloc::format!("{nf:x}").parse(), Ok(nf));
+        assert_eq!(alloc::format!("{cm:x}").parse(), Ok(cm));
+        assert_eq!(
+            alloc::format!("{nf:x}"),
+            alloc::format!("{:x}", Tachygram::from(nf)),
+            "prints as the tachygram it becomes"
+        );
+    }
+
     #[test]
     fn debug_nullifier_trapdoor_redacts_value() {
         let psi = NullifierTrapdoor::from(Fp::from(0xCAFEu64));
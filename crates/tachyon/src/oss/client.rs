@@ -0,0 +1,271 @@
+//! The wallet side: what is delegated where, and what has been reported
+//! spent.
+//!
+//! ## Encoding
+//!
+//! A [`Package`] is `id || compactsize(n) || key*n`, with the note id a
+//! little-endian `u64` and each key as [`NotePrefixedKey::write`].
+//!
+//! [`Delegations`] persist as `compactsize(n) || entry*n`, each entry
+//! `id || service || first || last || spent`: the note id a little-endian
+//! `u64`, the service and the delegated epoch bounds little-endian `u32`s,
+//! and `spent` a zero byte, or a one byte then the epoch as a little-endian
+//! `u32`. Entries are in increasing id order.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::RangeInclusive;
+
+use corez::io::{self, Read, Write};
+use derive_more::{Debug, Eq as TotalEq, PartialEq};
+
+use super::Match;
+use crate::{
+    keys::{NoteMasterKey, NotePrefixedKey},
+    primitives::{EpochIndex, Tachygram},
+    serialization,
+};
+
+/// The prefix keys for one note, as handed to a service.
+#[derive(Clone, Debug, PartialEq, TotalEq)]
+pub struct Package {
+    /// The wallet's name for the note, echoed back in [`Match::id`].
+    pub id: u64,
+    /// Prefix keys covering the delegated epochs.
+    pub keys: Vec<NotePrefixedKey>,
+}
+
+impl Package {
+    /// Read a package. See the module-level encoding documentation.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let id = serialization::read_u64(&mut reader)?;
+        // lengths are attacker-controlled, so do not pre-allocate.
+        let mut keys = Vec::new();
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            keys.push(NotePrefixedKey::read(&mut reader)?);
+        }
+        Ok(Self { id, keys })
+    }
+
+    /// Write a package. See the module-level encoding documentation.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.id.to_le_bytes())?;
+        serialization::write_len(&mut writer, self.keys.len())?;
+        for key in &self.keys {
+            key.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// One note's delegation, as the wallet records it.
+#[derive(Clone, Debug, PartialEq, TotalEq)]
+pub struct Delegation {
+    /// The wallet's name for the service holding the keys.
+    pub service: u32,
+    /// The epochs the service can derive the note's nullifier for.
+    pub epochs: RangeInclusive<u32>,
+    /// The epoch the service reported the note spent in, if any.
+    pub spent: Option<EpochIndex>,
+}
+
+/// The wallet's record of its delegated notes.
+///
+/// [`apply`](Self::apply) takes a service's reports at its word: the service
+/// can claim a spend that never happened, though only in the epochs it was
+/// delegated. [`apply_verified`](Self::apply_verified) instead checks each
+/// report's tachygram against the nullifier the wallet derives from its own
+/// [`NoteMasterKey`].
+#[derive(Clone, Debug, Default, PartialEq, TotalEq)]
+pub struct Delegations {
+    notes: BTreeMap<u64, Delegation>,
+}
+
+impl Delegations {
+    /// Start with no delegated notes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            notes: BTreeMap::new(),
+        }
+    }
+
+    /// Delegate the note the wallet calls `id` to `service`, for `epochs`,
+    /// and package its prefix keys for the service.
+    ///
+    /// Replaces any earlier delegation of the note; a spend already reported
+    /// is kept. The earlier service still holds its keys, so a wallet moving
+    /// a note between services has exposed it to both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epochs` extends past the epoch space.
+    pub fn delegate(
+        &mut self,
+        id: u64,
+        service: u32,
+        mk: &NoteMasterKey,
+        epochs: RangeInclusive<u32>,
+    ) -> Package {
+        let keys = mk.derive_note_delegates(epochs.clone());
+        let spent = self.notes.get(&id).and_then(|delegation| delegation.spent);
+        self.notes.insert(
+            id,
+            Delegation {
+                service,
+                epochs,
+                spent,
+            },
+        );
+        Package { id, keys }
+    }
+
+    /// The delegation of the note the wallet calls `id`.
+    #[must_use]
+    pub fn get(&self, id: u64) -> Option<&Delegation> {
+        self.notes.get(&id)
+    }
+
+    /// Forget the note the wallet calls `id`, returning its delegation.
+    pub fn remove(&mut self, id: u64) -> Option<Delegation> {
+        self.notes.remove(&id)
+    }
+
+    /// Number of delegated notes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Whether no note is delegated.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// The delegated notes not yet reported spent.
+    pub fn unspent(&self) -> impl Iterator<Item = u64> + '_ {
+        self.notes
+            .iter()
+            .filter(|entry| entry.1.spent.is_none())
+            .map(|entry| *entry.0)
+    }
+
+    /// Apply the matches `service` reported, returning the notes newly
+    /// marked spent.
+    ///
+    /// Ignores matches for notes not delegated to `service`, or in epochs it
+    /// was not delegated, as the service could not have derived them.
+    pub fn apply(&mut self, service: u32, matches: &[Match<u64>]) -> Vec<u64> {
+        self.apply_if(service, matches, |_reported| true)
+    }
+
+    /// As [`apply`](Self::apply), also ignoring matches whose tachygram is
+    /// not the note's nullifier for the reported epoch.
+    ///
+    /// `mk_of` gives the master key of the note the wallet calls `id`; a
+    /// match for a note it has no key for is ignored.
+    pub fn apply_verified(
+        &mut self,
+        service: u32,
+        matches: &[Match<u64>],
+        mk_of: impl Fn(u64) -> Option<NoteMasterKey>,
+    ) -> Vec<u64> {
+        self.apply_if(service, matches, |reported| {
+            mk_of(reported.id).is_some_and(|mk| {
+                Tachygram::from(mk.derive_nullifier(reported.epoch)) == reported.tachygram
+            })
+        })
+    }
+
+    fn apply_if(
+        &mut self,
+        service: u32,
+        matches: &[Match<u64>],
+        accept: impl Fn(&Match<u64>) -> bool,
+    ) -> Vec<u64> {
+        let mut spent = Vec::new();
+        for reported in matches {
+            let Some(delegation) = self.notes.get_mut(&reported.id) else {
+                continue;
+            };
+            if delegation.service == service
+                && delegation.epochs.contains(&reported.epoch.0)
+                && delegation.spent.is_none()
+                && accept(reported)
+            {
+                delegation.spent = Some(reported.epoch);
+                spent.push(reported.id);
+            }
+        }
+        spent
+    }
+
+    /// Read delegations. See the module-level encoding documentation.
+    ///
+    /// Rejects entries out of id order, and empty epoch ranges.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut notes = BTreeMap::new();
+        let mut last_id = None;
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            let id = serialization::read_u64(&mut reader)?;
+            if last_id.is_some_and(|last| last >= id) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "delegations out of id order",
+                ));
+            }
+            last_id = Some(id);
+
+            let service = serialization::read_u32(&mut reader)?;
+            let first = serialization::read_u32(&mut reader)?;
+            let last = serialization::read_u32(&mut reader)?;
+            let epochs = first..=last;
+            if epochs.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "empty delegated epoch range",
+                ));
+            }
+            let mut flag = [0u8; 1];
+            reader.read_exact(&mut flag)?;
+            let spent = match u8::from_le_bytes(flag) {
+                0 => None,
+                1 => Some(EpochIndex(serialization::read_u32(&mut reader)?)),
+                _other => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid spent flag",
+                    ));
+                },
+            };
+            notes.insert(
+                id,
+                Delegation {
+                    service,
+                    epochs,
+                    spent,
+                },
+            );
+        }
+        Ok(Self { notes })
+    }
+
+    /// Write delegations. See the module-level encoding documentation.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_len(&mut writer, self.notes.len())?;
+        for (id, delegation) in &self.notes {
+            writer.write_all(&id.to_le_bytes())?;
+            writer.write_all(&delegation.service.to_le_bytes())?;
+            writer.write_all(&delegation.epochs.start().to_le_bytes())?;
+            writer.write_all(&delegation.epochs.end().to_le_bytes())?;
+            match delegation.spent {
+                None => writer.write_all(&[0])?,
+                Some(epoch) => {
+                    writer.write_all(&[1])?;
+                    writer.write_all(&epoch.0.to_le_bytes())?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+//! Oblivious synchronization.
+//!
+//! A wallet delegates the work of finding its spent notes to a service
+//! (OSS). For each note it hands over prefix keys from
+//! [`NoteMasterKey::derive_note_delegates`](crate::keys::NoteMasterKey::derive_note_delegates),
+//! which let the service derive the note's nullifier for the epochs they
+//! cover and no others. The service cannot spend the note, and learns nothing
+//! of it until one of those nullifiers is published.
+//!
+//! The service keeps a [`Scanner`]; the wallet keeps [`Delegations`], which
+//! packages the keys for each service and marks notes spent as the services
+//! report them.
+
+mod client;
+mod scanner;
+
+pub use client::{Delegation, Delegations, Package};
+pub use scanner::{Match, Scanner, Watch};
+
+#[cfg(test)]
+mod tests;
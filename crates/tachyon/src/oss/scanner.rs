@@ -0,0 +1,122 @@
+//! The service side: matching published tachygrams against delegated
+//! nullifiers.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use derive_more::{Debug, Eq as TotalEq, PartialEq};
+
+use crate::{
+    keys::NotePrefixedKey,
+    primitives::{EpochIndex, Tachygram},
+};
+
+/// A delegated note's nullifier, seen published.
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub struct Match<Id> {
+    /// The wallet's name for the note.
+    pub id: Id,
+    /// The epoch the nullifier is for.
+    pub epoch: EpochIndex,
+    /// The nullifier, as published.
+    pub tachygram: Tachygram,
+}
+
+/// Delegated notes, and the prefix keys to watch them with.
+///
+/// A spend publishes the note's nullifier for the epoch it lands in, whichever
+/// epoch of the anchor window its stamp is anchored in. To scan an epoch, the
+/// service derives each delegated note's nullifier for it once with
+/// [`watch`](Self::watch), then matches the epoch's blocks against them.
+#[derive(Clone, Debug)]
+pub struct Scanner<Id> {
+    delegates: Vec<(Id, NotePrefixedKey)>,
+}
+
+impl<Id: Copy> Default for Scanner<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: Copy> Scanner<Id> {
+    /// Start with no delegated notes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            delegates: Vec::new(),
+        }
+    }
+
+    /// Watch the note the wallet calls `id`, over the epochs `keys` cover.
+    pub fn delegate(&mut self, id: Id, keys: impl IntoIterator<Item = NotePrefixedKey>) {
+        self.delegates.extend(keys.into_iter().map(|key| (id, key)));
+    }
+
+    /// Number of prefix keys held.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.delegates.len()
+    }
+
+    /// Whether no prefix key is held.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.delegates.is_empty()
+    }
+
+    /// Derive the nullifiers of every note delegated for `epoch`.
+    #[must_use]
+    pub fn watch(&self, epoch: EpochIndex) -> Watch<Id> {
+        let nullifiers = self
+            .delegates
+            .iter()
+            .filter(|delegate| delegate.1.range().contains(&epoch.0))
+            .map(|delegate| {
+                (
+                    Tachygram::from(delegate.1.derive_nullifier(epoch)),
+                    delegate.0,
+                )
+            })
+            .collect();
+        Watch { epoch, nullifiers }
+    }
+
+    /// Drop the prefix keys that cover no epoch from `epoch` on.
+    pub fn retire(&mut self, epoch: EpochIndex) {
+        self.delegates
+            .retain(|delegate| *delegate.1.range().end() >= epoch.0);
+    }
+}
+
+/// The nullifiers delegated notes publish in one epoch.
+#[derive(Clone, Debug)]
+pub struct Watch<Id> {
+    epoch: EpochIndex,
+    nullifiers: BTreeMap<Tachygram, Id>,
+}
+
+impl<Id: Copy> Watch<Id> {
+    /// The epoch watched.
+    #[must_use]
+    pub const fn epoch(&self) -> EpochIndex {
+        self.epoch
+    }
+
+    /// The delegated nullifiers among `tachygrams`, as published in a block
+    /// of the epoch.
+    pub fn scan<'tachygram>(
+        &self,
+        tachygrams: impl IntoIterator<Item = &'tachygram Tachygram>,
+    ) -> Vec<Match<Id>> {
+        tachygrams
+            .into_iter()
+            .filter_map(|tachygram| {
+                self.nullifiers.get(tachygram).map(|&id| Match {
+                    id,
+                    epoch: self.epoch,
+                    tachygram: *tachygram,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+use alloc::{vec, vec::Vec};
+
+use ff::Field as _;
+use pasta_curves::Fp;
+use rand::{SeedableRng as _, rngs::StdRng};
+
+use super::*;
+use crate::{
+    fixtures::WalletSim,
+    primitives::{EpochIndex, Tachygram},
+};
+
+/// A delegated note's nullifier is found in the epochs its keys cover, and
+/// nowhere else.
+#[test]
+fn scanner_finds_delegated_nullifiers() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let spent = wallet.random_note(300);
+    let other = wallet.random_note(200);
+
+    let mut scanner = Scanner::new();
+    scanner.delegate(7u32, wallet.mk(&spent).derive_note_delegates(0..=7));
+    scanner.delegate(8u32, wallet.mk(&other).derive_note_delegates(4..=5));
+    assert!(!scanner.is_empty());
+
+    let nf = Tachygram::from(wallet.nf_at(&spent, EpochIndex(3)));
+    let noise = Tachygram::from(Fp::random(&mut *rng));
+    let watch = scanner.watch(EpochIndex(3));
+    assert_eq!(watch.epoch(), EpochIndex(3));
+    assert_eq!(
+        watch.scan(&[noise, nf]),
+        vec![Match {
+            id: 7,
+            epoch: EpochIndex(3),
+            tachygram: nf,
+        }]
+    );
+
+    let later = Tachygram::from(wallet.nf_at(&spent, EpochIndex(9)));
+    assert!(
+        scanner.watch(EpochIndex(9)).scan(&[later]).is_empty(),
+        "epoch 9 is not delegated"
+    );
+
+    let held = scanner.len();
+    scanner.retire(EpochIndex(6));
+    assert!(scanner.len() < held, "keys for epochs 4..=5 are dropped");
+    assert!(
+        scanner
+            .watch(EpochIndex(5))
+            .scan(&[Tachygram::from(wallet.nf_at(&other, EpochIndex(5)))])
+            .is_empty(),
+        "retired keys no longer match"
+    );
+}
+
+/// A wallet packages a note's keys for a service, applies the service's
+/// report, and restores its record from the persisted encoding.
+#[test]
+fn delegations_apply_reports_and_round_trip() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let note = wallet.random_note(300);
+
+    let mut delegations = Delegations::new();
+    let package = delegations.delegate(1, 5, &wallet.mk(&note), 0..=7);
+    delegations.delegate(2, 6, &wallet.mk(&wallet.random_note(200)), 0..=7);
+
+    let mut sent = Vec::new();
+    package.write(&mut sent).expect("write");
+    let received = Package::read(sent.as_slice()).expect("read");
+    assert_eq!(received, package);
+    let mut scanner = Scanner::new();
+    scanner.delegate(received.id, received.keys);
+
+    let nf = Tachygram::from(wallet.nf_at(&note, EpochIndex(3)));
+    let matches = scanner.watch(EpochIndex(3)).scan(&[nf]);
+    assert!(
+        delegations.apply(6, &matches).is_empty(),
+        "note 1 is not delegated to service 6"
+    );
+    assert_eq!(delegations.apply(5, &matches), vec![1]);
+    assert!(delegations.apply(5, &matches).is_empty(), "already spent");
+    assert_eq!(delegations.unspent().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(
+        delegations.get(1).map(|delegation| delegation.spent),
+        Some(Some(EpochIndex(3)))
+    );
+
+    let mut persisted = Vec::new();
+    delegations.write(&mut persisted).expect("write");
+    assert_eq!(
+        Delegations::read(persisted.as_slice()).expect("read"),
+        delegations
+    );
+}
+
+/// A wallet that verifies reports ignores a claimed spend whose tachygram is
+/// not the note's nullifier.
+#[test]
+fn delegations_reject_forged_reports() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let note = wallet.random_note(300);
+    let mk = wallet.mk(&note);
+
+    let mut delegations = Delegations::new();
+    delegations.delegate(1, 5, &mk, 0..=7);
+    let mk_of = |id: u64| (id == 1).then_some(mk);
+
+    let forged = Match {
+        id: 1,
+        epoch: EpochIndex(3),
+        tachygram: Tachygram::from(Fp::random(&mut *rng)),
+    };
+    assert!(
+        delegations.apply_verified(5, &[forged], mk_of).is_empty(),
+        "a forged tachygram is ignored"
+    );
+    assert_eq!(delegations.unspent().collect::<Vec<_>>(), vec![1]);
+
+    let genuine = Match {
+        tachygram: Tachygram::from(wallet.nf_at(&note, EpochIndex(3))),
+        ..forged
+    };
+    assert_eq!(delegations.apply_verified(5, &[genuine], mk_of), vec![1]);
+}
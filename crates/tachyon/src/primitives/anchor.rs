@@ -1,10 +1,13 @@
+use alloc::{vec, vec::Vec};
+use core::{fmt, ops::RangeInclusive, str::FromStr};
+
 use corez::io::{self, Read, Write};
 use derive_more::{Debug, Eq as TotalEq, From, Into, PartialEq};
 use ff::Field as _;
 use group::Curve as _;
-use pasta_curves::{Eq, Fp, arithmetic::CurveAffine as _};
+use pasta_curves::{Eq, EqAffine, Fp, arithmetic::CurveAffine as _};
 
-use super::{EpochIndex, TachygramSetCommit};
+use super::{EpochIndex, ParseHexError, TachygramSetCommit};
 use crate::{digest::poseidon, serialization};
 
 /// Running anchor over the consensus state.
@@ -37,6 +40,32 @@ impl Anchor {
         Self(poseidon::anchor_stamp_step(self.0, coords))
     }
 
+    /// Advance the anchor by absorbing each stamp's commit, in order.
+    ///
+    /// Equivalent to [`next_stamp`](Self::next_stamp) on each commit in turn,
+    /// but the commits are converted to affine together, with one field
+    /// inversion for the batch rather than one per stamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any commit is the identity point.
+    #[must_use]
+    pub fn next_stamps(self, stamp_commits: &[TachygramSetCommit]) -> Self {
+        let points: Vec<Eq> = stamp_commits
+            .iter()
+            .map(|&commit| Eq::from(commit))
+            .collect();
+        let mut affine = vec![EqAffine::default(); points.len()];
+        Eq::batch_normalize(&points, &mut affine);
+        affine.iter().fold(self, |anchor, point| {
+            #[expect(clippy::expect_used, reason = "documented panic")]
+            let coords = point
+                .coordinates()
+                .expect("must not be identity commitment");
+            Self(poseidon::anchor_stamp_step(anchor.0, coords))
+        })
+    }
+
     /// Advance the anchor through one empty block (zero stamps).
     #[must_use]
     pub fn next_empty(self) -> Self {
@@ -50,6 +79,15 @@ impl Anchor {
         Self(poseidon::anchor_epoch_step(self.0, new_epoch))
     }
 
+    /// Whether the anchor falls within the range `policy` accepts for the
+    /// landing block.
+    ///
+    /// An anchor unknown to the policy is never valid.
+    #[must_use]
+    pub fn is_valid_for(&self, policy: &AnchorPolicy<'_>) -> bool {
+        (policy.epoch_of)(self).is_some_and(|epoch| policy.epochs().contains(&epoch))
+    }
+
     /// Read a 32-byte anchor.
     pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
         serialization::read_fp(&mut reader).map(Self)
@@ -61,6 +99,52 @@ impl Anchor {
     }
 }
 
+impl fmt::LowerHex for Anchor {
+    /// The 32-byte wire encoding, as for RPC and explorers.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialization::fmt_fp_hex(&self.0, formatter)
+    }
+}
+
+impl FromStr for Anchor {
+    type Err = ParseHexError;
+
+    /// Parse the hex [`LowerHex`](fmt::LowerHex) prints.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        serialization::parse_fp_hex(hex)
+            .map(Self)
+            .ok_or(ParseHexError)
+    }
+}
+
+/// The anchors a block accepts on the stamps it includes.
+///
+/// An anchor is a hash, so its epoch is known only to a node that followed
+/// the chain producing it; `epoch_of` supplies that knowledge. A stamp is
+/// accepted if its anchor is from the landing block's epoch or one of the
+/// `window` epochs before it. Consensus rejects a tachygram repeated within
+/// the current or preceding epoch, so a window of 1 matches the span over
+/// which validators track tachygrams.
+#[derive(Clone, Copy, Debug)]
+pub struct AnchorPolicy<'chain> {
+    /// Epoch of the landing block.
+    pub current_epoch: EpochIndex,
+    /// Number of epochs before the current one still accepted.
+    pub window: u32,
+    /// The epoch in which the chain produced an anchor, if it did.
+    #[debug(skip)]
+    pub epoch_of: &'chain dyn Fn(&Anchor) -> Option<EpochIndex>,
+}
+
+impl AnchorPolicy<'_> {
+    /// The epochs whose anchors the landing block accepts: the current epoch
+    /// and the `window` before it, or as many as there are.
+    #[must_use]
+    pub const fn epochs(&self) -> RangeInclusive<EpochIndex> {
+        EpochIndex(self.current_epoch.0.saturating_sub(self.window))..=self.current_epoch
+    }
+}
+
 impl Default for Anchor {
     /// The genesis epoch boundary.
     fn default() -> Self {
@@ -70,6 +154,8 @@ impl Default for Anchor {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{borrow::ToOwned as _, format, string::String};
+
     use rand::{SeedableRng as _, rngs::StdRng};
 
     use super::*;
@@ -87,6 +173,21 @@ mod tests {
         assert_eq!(run_one, run_two);
     }
 
+    /// Absorbing a batch of stamps matches absorbing them one at a time.
+    #[test]
+    fn next_stamps_matches_next_stamp() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let commits: Vec<TachygramSetCommit> = (0..5)
+            .map(|_| TachygramSetPoly::from_iter([Tachygram::from(Fp::random(&mut *rng))]).commit())
+            .collect();
+
+        let one_by_one = commits.iter().fold(Anchor::default(), |anchor, commit| {
+            anchor.next_stamp(commit)
+        });
+        assert_eq!(Anchor::default().next_stamps(&commits), one_by_one);
+        assert_eq!(Anchor::default().next_stamps(&[]), Anchor::default());
+    }
+
     /// Two distinct stamp commits absorb to distinct anchors.
     #[test]
     fn distinct_stamps_distinct_anchors() {
@@ -112,6 +213,76 @@ mod tests {
         assert_ne!(forward, reverse);
     }
 
+    /// Anchors are accepted from the current epoch and the window before it.
+    #[test]
+    fn anchor_policy_window() {
+        let old = Anchor::default();
+        let recent = old.next_epoch(EpochIndex(1));
+        let current = recent.next_epoch(EpochIndex(2));
+        let unknown = current.next_empty();
+        let epoch_of = |anchor: &Anchor| {
+            [(old, 0), (recent, 1), (current, 2)]
+                .into_iter()
+                .find(|&(known, _)| known == *anchor)
+                .map(|(_, epoch)| EpochIndex(epoch))
+        };
+        let policy = AnchorPolicy {
+            current_epoch: EpochIndex(2),
+            window: 1,
+            epoch_of: &epoch_of,
+        };
+
+        assert!(current.is_valid_for(&policy), "current epoch is accepted");
+        assert!(recent.is_valid_for(&policy), "preceding epoch is accepted");
+        assert!(!old.is_valid_for(&policy), "older epoch is rejected");
+        assert!(!unknown.is_valid_for(&policy), "unknown anchor is rejected");
+        assert_eq!(policy.epochs(), EpochIndex(1)..=EpochIndex(2));
+        assert!(
+            !current.is_valid_for(&AnchorPolicy {
+                current_epoch: EpochIndex(1),
+                ..policy
+            }),
+            "future epoch is rejected"
+        );
+    }
+
+    /// Anchors and tachygrams print as the hex of their wire encoding.
+    #[test]
+    fn lower_hex_is_wire_encoding() {
+        let one = "01".to_owned() + &"00".repeat(31);
+        assert_eq!(format!("{:x}", Tachygram::from(Fp::ONE)), one);
+        assert_eq!(format!("{:x}", Anchor(Fp::ONE)), one);
+
+        let mut wire = Vec::new();
+        Anchor::default().write(&mut wire).expect("write");
+        let hex: String = wire.iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(format!("{:x}", Anchor::default()), hex);
+    }
+
+    /// Parsing inverts the hex encoding, and rejects anything else.
+    #[test]
+    fn from_str_round_trips_lower_hex() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let anchor = Anchor(Fp::random(&mut *rng));
+        let tachygram = Tachygram::from(Fp::random(&mut *rng));
+        let epoch = EpochIndex(0x0102_0304);
+
+        assert_eq!(format!("{anchor:x}").parse(), Ok(anchor));
+        assert_eq!(format!("{tachygram:x}").parse(), Ok(tachygram));
+        assert_eq!(format!("{epoch:x}"), "04030201");
+        assert_eq!(format!("{epoch:x}").parse(), Ok(epoch));
+        assert_eq!(
+            format!("{anchor:x}").to_uppercase().parse(),
+            Ok(anchor),
+            "either case is accepted"
+        );
+
+        assert_eq!("0403020".parse::<EpochIndex>(), Err(ParseHexError));
+        assert_eq!("+4030201".parse::<EpochIndex>(), Err(ParseHexError));
+        assert_eq!("ff".repeat(32).parse::<Anchor>(), Err(ParseHexError));
+        assert_eq!("0g".repeat(32).parse::<Tachygram>(), Err(ParseHexError));
+    }
+
     /// An empty-block tick changes the anchor.
     #[test]
     fn next_empty_advances_anchor() {
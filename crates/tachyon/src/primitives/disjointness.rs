@@ -0,0 +1,199 @@
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+use core::iter;
+
+use derive_more::Debug;
+use ff::Field as _;
+use pasta_curves::Fp;
+use ragu::{Polynomial, poly_with_roots};
+
+use super::Tachygram;
+
+/// Witness that two tachygram sets share no member.
+///
+/// For the set polynomials $L$ and $R$ of the two sets, holds cofactors $U$
+/// and $V$ with $U \cdot L + V \cdot R = 1$, and the products $U \cdot L$ and
+/// $V \cdot R$. A shared member is a common root of $L$ and $R$, which would
+/// divide the left side but not $1$, so such cofactors exist exactly when the
+/// sets are disjoint.
+///
+/// For sets that overlap, [`new`](Self::new) finds the cofactors whose
+/// combination is the greatest common divisor of $L$ and $R$ instead, which
+/// the merge step rejects.
+#[derive(Clone, Debug)]
+pub struct TachygramDisjointness {
+    /// $U$.
+    pub(crate) left_cofactor: Polynomial,
+    /// $U \cdot L$.
+    pub(crate) left_product: Polynomial,
+    /// $V$.
+    pub(crate) right_cofactor: Polynomial,
+    /// $V \cdot R$.
+    pub(crate) right_product: Polynomial,
+}
+
+impl TachygramDisjointness {
+    /// Find the cofactors for the sets `left` and `right` by the extended
+    /// Euclidean algorithm.
+    ///
+    /// This takes time quadratic in the size of the sets.
+    #[must_use]
+    pub fn new(
+        left: impl IntoIterator<Item = Tachygram>,
+        right: impl IntoIterator<Item = Tachygram>,
+    ) -> Self {
+        let left_roots: Vec<Fp> = left.into_iter().map(Fp::from).collect();
+        let right_roots: Vec<Fp> = right.into_iter().map(Fp::from).collect();
+        let left_set = trim(poly_with_roots(&left_roots));
+        let right_set = trim(poly_with_roots(&right_roots));
+        let (left_cofactor, right_cofactor) = cofactors(&left_set, &right_set);
+        Self {
+            left_product: Polynomial::from_coeffs(mul(&left_cofactor, &left_set)),
+            right_product: Polynomial::from_coeffs(mul(&right_cofactor, &right_set)),
+            left_cofactor: Polynomial::from_coeffs(left_cofactor),
+            right_cofactor: Polynomial::from_coeffs(right_cofactor),
+        }
+    }
+}
+
+/// Cofactors $U, V$ with $U \cdot L + V \cdot R$ the monic greatest common
+/// divisor of `left` and `right`, which is $1$ when they share no root.
+///
+/// Coefficients are in ascending degree, as throughout this module.
+fn cofactors(left: &[Fp], right: &[Fp]) -> (Vec<Fp>, Vec<Fp>) {
+    // Invariant: `prev_rem = prev_left·L + prev_right·R`, and likewise for
+    // `rem`.
+    let (mut prev_rem, mut rem) = (left.to_vec(), right.to_vec());
+    let (mut prev_left, mut cur_left) = (vec![Fp::ONE], Vec::new());
+    let (mut prev_right, mut cur_right) = (Vec::new(), vec![Fp::ONE]);
+    while !rem.is_empty() {
+        let (quotient, next_rem) = div_rem(&prev_rem, &rem);
+        let next_left = sub(&prev_left, &mul(&quotient, &cur_left));
+        let next_right = sub(&prev_right, &mul(&quotient, &cur_right));
+        (prev_rem, rem) = (rem, next_rem);
+        (prev_left, cur_left) = (cur_left, next_left);
+        (prev_right, cur_right) = (cur_right, next_right);
+    }
+
+    // Scale the divisor to be monic.
+    let scale = prev_rem
+        .last()
+        .and_then(|lead| Option::<Fp>::from(lead.invert()))
+        .unwrap_or(Fp::ONE);
+    (
+        prev_left.into_iter().map(|coeff| coeff * scale).collect(),
+        prev_right.into_iter().map(|coeff| coeff * scale).collect(),
+    )
+}
+
+/// Drop the zero coefficients above the leading one.
+fn trim(mut coeffs: Vec<Fp>) -> Vec<Fp> {
+    while coeffs.last() == Some(&Fp::ZERO) {
+        coeffs.pop();
+    }
+    coeffs
+}
+
+/// `lhs · rhs`.
+fn mul(lhs: &[Fp], rhs: &[Fp]) -> Vec<Fp> {
+    let Some(len) = (lhs.len() + rhs.len()).checked_sub(1) else {
+        return Vec::new();
+    };
+    let mut product = vec![Fp::ZERO; len];
+    for (shift, &scale) in lhs.iter().enumerate() {
+        for (coeff, &term) in product.iter_mut().skip(shift).zip(rhs) {
+            *coeff += scale * term;
+        }
+    }
+    trim(product)
+}
+
+/// `lhs − rhs`.
+fn sub(lhs: &[Fp], rhs: &[Fp]) -> Vec<Fp> {
+    trim(
+        lhs.iter()
+            .copied()
+            .chain(iter::repeat(Fp::ZERO))
+            .zip(rhs.iter().copied().chain(iter::repeat(Fp::ZERO)))
+            .take(lhs.len().max(rhs.len()))
+            .map(|(minuend, subtrahend)| minuend - subtrahend)
+            .collect(),
+    )
+}
+
+/// Quotient and remainder of `dividend` by the nonzero, trimmed `divisor`.
+fn div_rem(dividend: &[Fp], divisor: &[Fp]) -> (Vec<Fp>, Vec<Fp>) {
+    let mut remainder = dividend.to_vec();
+    let Some(lead_inv) = divisor
+        .last()
+        .and_then(|lead| Option::<Fp>::from(lead.invert()))
+    else {
+        return (Vec::new(), trim(remainder));
+    };
+    let Some(quotient_len) = (dividend.len() + 1).checked_sub(divisor.len()) else {
+        return (Vec::new(), trim(remainder));
+    };
+    let mut quotient = vec![Fp::ZERO; quotient_len];
+    for (shift, slot) in quotient.iter_mut().enumerate().rev() {
+        let top = remainder
+            .get(shift + divisor.len() - 1)
+            .copied()
+            .unwrap_or(Fp::ZERO);
+        let factor = top * lead_inv;
+        *slot = factor;
+        for (coeff, &term) in remainder.iter_mut().skip(shift).zip(divisor) {
+            *coeff -= factor * term;
+        }
+    }
+    (trim(quotient), trim(remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+    use crate::primitives::TachygramSetPoly;
+
+    fn random_tachygrams(rng: &mut StdRng, count: usize) -> Vec<Tachygram> {
+        iter::repeat_with(|| Tachygram::from(Fp::random(&mut *rng)))
+            .take(count)
+            .collect()
+    }
+
+    /// For disjoint sets the cofactors combine to $1$; for sets sharing a
+    /// member they cannot.
+    #[test]
+    fn cofactors_combine_to_one_only_for_disjoint_sets() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let left = random_tachygrams(rng, 5);
+        let right = random_tachygrams(rng, 3);
+        let point = Fp::random(&mut *rng);
+
+        let disjoint = TachygramDisjointness::new(left.iter().copied(), right.iter().copied());
+        let left_set: TachygramSetPoly = left.iter().copied().collect();
+        let right_set: TachygramSetPoly = right.iter().copied().collect();
+        assert_eq!(
+            disjoint.left_product.eval(point),
+            disjoint.left_cofactor.eval(point) * left_set.eval(point)
+        );
+        assert_eq!(
+            disjoint.right_product.eval(point),
+            disjoint.right_cofactor.eval(point) * right_set.eval(point)
+        );
+        assert_eq!(
+            disjoint.left_product.eval(point) + disjoint.right_product.eval(point),
+            Fp::ONE
+        );
+
+        let overlapping = TachygramDisjointness::new(
+            left.iter().copied(),
+            right.iter().copied().chain(left.first().copied()),
+        );
+        assert_ne!(
+            overlapping.left_product.eval(point) + overlapping.right_product.eval(point),
+            Fp::ONE
+        );
+    }
+}
@@ -1,6 +1,11 @@
+use core::{fmt, str::FromStr};
+
 use derive_more::{Debug, Eq as TotalEq, From, Into, PartialEq};
 use pasta_curves::Fp;
 
+use super::ParseHexError;
+use crate::serialization;
+
 /// A tachyon epoch — a point in the accumulator's history.
 ///
 /// The tachyon accumulator evolves as tachygrams are included. Each
@@ -21,6 +26,24 @@ impl EpochIndex {
     }
 }
 
+impl fmt::LowerHex for EpochIndex {
+    /// The 4-byte little-endian wire encoding, as for RPC and explorers.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialization::fmt_hex(&self.0.to_le_bytes(), formatter)
+    }
+}
+
+impl FromStr for EpochIndex {
+    type Err = ParseHexError;
+
+    /// Parse the hex [`LowerHex`](fmt::LowerHex) prints.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        serialization::parse_hex(hex)
+            .map(|bytes| Self(u32::from_le_bytes(bytes)))
+            .ok_or(ParseHexError)
+    }
+}
+
 impl From<EpochIndex> for Fp {
     fn from(epoch: EpochIndex) -> Self {
         Self::from(u64::from(epoch.0))
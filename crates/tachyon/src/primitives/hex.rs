@@ -0,0 +1,9 @@
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+
+/// Error parsing a primitive from the hex its `LowerHex` impl prints.
+///
+/// The string had the wrong length, a non-hex digit, or decoded to a
+/// non-canonical encoding.
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[display("invalid hex encoding")]
+pub struct ParseHexError;
@@ -1,17 +1,22 @@
 mod action_digest;
 mod anchor;
 mod block_height;
+mod disjointness;
 pub mod effect;
 mod epoch;
+mod hex;
 mod seq;
 mod sets;
 mod tachygram;
 
 pub use action_digest::{ActionDigest, ActionDigestError};
-pub use anchor::Anchor;
+pub use anchor::{Anchor, AnchorPolicy};
 pub use block_height::BlockHeight;
+pub use disjointness::TachygramDisjointness;
 pub use effect::Effect;
 pub use epoch::EpochIndex;
+pub use hex::ParseHexError;
 pub use seq::{NfSeqCommit, NfSeqPoly};
+pub(crate) use sets::ActionSetAccumulator;
 pub use sets::{ActionSetCommit, ActionSetPoly, TachygramSetCommit, TachygramSetPoly};
 pub use tachygram::Tachygram;
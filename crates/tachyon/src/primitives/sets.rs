@@ -1,8 +1,9 @@
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 
 use derive_more::{Debug, Eq as TotalEq, From, Into, PartialEq};
+use ff::Field as _;
 use pasta_curves::{Eq, Fp};
 use ragu::{Polynomial, poly_with_roots};
 
@@ -53,6 +54,39 @@ impl ActionSetPoly {
     }
 }
 
+/// An [`ActionSetPoly`] built up one member at a time, for a verifier that
+/// sees a stamp's actions as they arrive.
+///
+/// Holds the coefficients of $\prod_i (X - d_i)$ in ascending degree, and
+/// multiplies in one root per [`insert`](Self::insert).
+#[derive(Clone, Debug)]
+pub(crate) struct ActionSetAccumulator(Vec<Fp>);
+
+impl ActionSetAccumulator {
+    /// The polynomial of the empty set, the constant $1$.
+    pub(crate) fn new() -> Self {
+        Self(vec![Fp::ONE])
+    }
+
+    /// Add `digest` to the set.
+    pub(crate) fn insert(&mut self, digest: ActionDigest) {
+        let root = Fp::from(digest);
+        let mut lower = Fp::ZERO;
+        for coeff in &mut self.0 {
+            let current = *coeff;
+            *coeff = lower - root * current;
+            lower = current;
+        }
+        self.0.push(lower);
+    }
+}
+
+impl From<ActionSetAccumulator> for ActionSetPoly {
+    fn from(accumulator: ActionSetAccumulator) -> Self {
+        Self(Polynomial::from_coeffs(accumulator.0))
+    }
+}
+
 impl FromIterator<ActionDigest> for ActionSetPoly {
     fn from_iter<I: IntoIterator<Item = ActionDigest>>(iter: I) -> Self {
         let roots: Vec<Fp> = iter.into_iter().map(Fp::from).collect();
@@ -66,3 +100,28 @@ impl FromIterator<Tachygram> for TachygramSetPoly {
         Self(Polynomial::from_coeffs(poly_with_roots(&roots)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+
+    /// Inserting digests one at a time builds the polynomial collecting them
+    /// does.
+    #[test]
+    fn accumulator_matches_collected_set() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let digests: Vec<ActionDigest> = (0..5)
+            .map(|_| ActionDigest::from(Fp::random(&mut *rng)))
+            .collect();
+
+        let mut accumulator = ActionSetAccumulator::new();
+        for &digest in &digests {
+            accumulator.insert(digest);
+        }
+        let folded = ActionSetPoly::from(accumulator);
+        let collected: ActionSetPoly = digests.iter().copied().collect();
+        assert_eq!(folded.commit(), collected.commit());
+    }
+}
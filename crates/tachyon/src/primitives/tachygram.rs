@@ -1,9 +1,13 @@
-use core::cmp::Ordering;
+use core::{cmp::Ordering, fmt, str::FromStr};
 
+use corez::io::{self, Read, Write};
 use derive_more::{Debug, Eq as TotalEq, From, Into, PartialEq};
 use ff::PrimeField as _;
 use pasta_curves::Fp;
 
+use super::ParseHexError;
+use crate::serialization;
+
 /// A tachygram is a field element ($\mathbb{F}_p$) representing either a
 /// note commitment or a nullifier in the Tachyon polynomial accumulator.
 ///
@@ -21,6 +25,18 @@ use pasta_curves::Fp;
 #[derive(Clone, Copy, Debug, From, Into, PartialEq, TotalEq)]
 pub struct Tachygram(Fp);
 
+impl Tachygram {
+    /// Read a 32-byte tachygram.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        serialization::read_fp(&mut reader).map(Self)
+    }
+
+    /// Write a 32-byte tachygram.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_fp(&mut writer, &self.0)
+    }
+}
+
 impl PartialOrd for Tachygram {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -35,3 +51,21 @@ impl Ord for Tachygram {
         self.0.to_repr().as_ref().cmp(other.0.to_repr().as_ref())
     }
 }
+
+impl fmt::LowerHex for Tachygram {
+    /// The 32-byte wire encoding, as for RPC and explorers.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialization::fmt_fp_hex(&self.0, formatter)
+    }
+}
+
+impl FromStr for Tachygram {
+    type Err = ParseHexError;
+
+    /// Parse the hex [`LowerHex`](fmt::LowerHex) prints.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        serialization::parse_fp_hex(hex)
+            .map(Self)
+            .ok_or(ParseHexError)
+    }
+}
@@ -4,8 +4,8 @@
 //! signatures. This module re-exports reddsa types under Tachyon-specific
 //! names so the rest of the crate avoids direct `reddsa::orchard` imports.
 
-use ::reddsa::orchard;
 pub(crate) use ::reddsa::{Error, Signature, SigningKey, VerificationKey, VerificationKeyBytes};
+use ::reddsa::{batch, orchard};
 
 /// RedPallas signature scheme for action authorization.
 ///
@@ -14,3 +14,9 @@ pub(crate) type ActionAuth = orchard::SpendAuth;
 
 /// RedPallas signature scheme for value-balance binding.
 pub(crate) type BindingAuth = orchard::Binding;
+
+/// Batch verifier over action and binding signatures.
+pub(crate) type BatchVerifier = batch::Verifier<ActionAuth, BindingAuth>;
+
+/// An action or binding signature queued for batch verification.
+pub(crate) type BatchItem = batch::Item<ActionAuth, BindingAuth>;
@@ -177,6 +177,16 @@ impl CompactSize {
         })
     }
 
+    /// Number of bytes this [`CompactSize`] encodes to.
+    pub(crate) const fn encoded_len(self) -> usize {
+        match self {
+            Self::OneByte(_) => 1,
+            Self::TwoBytes(_) => 3,
+            Self::FourBytes(_) => 5,
+            Self::EightBytes(_) => 9,
+        }
+    }
+
     /// Write this [`CompactSize`] to `writer`.
     pub(crate) fn write<W: Write>(self, mut writer: W) -> io::Result<()> {
         match self {
@@ -6,6 +6,8 @@
 
 #![allow(dead_code, reason = "may not be used")]
 
+use core::fmt;
+
 use corez::io::{self, Read, Write};
 use ff::PrimeField as _;
 use pasta_curves::{EpAffine, EqAffine, Fp, Fq, group::GroupEncoding as _};
@@ -36,6 +38,69 @@ pub(crate) fn write_compactsize<W: Write>(mut writer: W, value: u64) -> io::Resu
     Ok(())
 }
 
+/// Write a collection length as a compactsize.
+pub(crate) fn write_len<W: Write>(writer: W, len: usize) -> io::Result<()> {
+    write_compactsize(
+        writer,
+        u64::try_from(len)
+            .map_err(|_err| io::Error::new(io::ErrorKind::InvalidData, "length exceeds u64"))?,
+    )
+}
+
+/// Read a little-endian `u32`.
+pub(crate) fn read_u32<R: Read>(mut reader: R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Read a little-endian `u64`.
+pub(crate) fn read_u64<R: Read>(mut reader: R) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Number of bytes a compactsize encoding of `len` takes.
+pub(crate) fn compactsize_len(len: usize) -> usize {
+    CompactSize::try_from(len).map_or(9, CompactSize::encoded_len)
+}
+
+/// Format bytes as lowercase hex, in the order given.
+pub(crate) fn fmt_hex(bytes: &[u8], formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    bytes
+        .iter()
+        .try_for_each(|byte| write!(formatter, "{byte:02x}"))
+}
+
+/// Format a field element's 32-byte encoding as lowercase hex, in wire
+/// (little-endian) byte order.
+pub(crate) fn fmt_fp_hex(fp: &Fp, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_hex(&fp.to_repr(), formatter)
+}
+
+/// Parse exactly `N` bytes from hex digits of either case, the inverse of
+/// [`fmt_hex`].
+pub(crate) fn parse_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != 2 * N {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = pair.iter().try_fold(0u8, |acc, &digit| {
+            let nibble = char::from(digit).to_digit(16)?;
+            Some((acc << 4) | u8::try_from(nibble).ok()?)
+        })?;
+    }
+    Some(bytes)
+}
+
+/// Parse a field element from the hex of its canonical 32-byte encoding, the
+/// inverse of [`fmt_fp_hex`].
+pub(crate) fn parse_fp_hex(hex: &str) -> Option<Fp> {
+    parse_hex(hex).and_then(|bytes| Option::from(Fp::from_repr(bytes)))
+}
+
 /// Read a Pallas base field element (`Fp`) from 32 bytes.
 pub(crate) fn read_fp<R: Read>(mut reader: R) -> io::Result<Fp> {
     let mut bytes = [0u8; 32];
@@ -63,11 +128,22 @@ pub(crate) fn write_fq<W: Write>(mut writer: W, fq: &Fq) -> io::Result<()> {
 }
 
 /// Read a Pallas affine curve point (`EpAffine`) from 32 compressed bytes.
+///
+/// Rejects any encoding that does not round-trip, so each point has exactly
+/// one accepted byte string.
 pub(crate) fn read_ep_affine<R: Read>(mut reader: R) -> io::Result<EpAffine> {
     let mut bytes = [0u8; 32];
     reader.read_exact(&mut bytes)?;
-    Option::from(EpAffine::from_bytes(&bytes))
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid curve point encoding"))
+    let point: EpAffine = Option::from(EpAffine::from_bytes(&bytes)).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "invalid curve point encoding")
+    })?;
+    if point.to_bytes() != bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "non-canonical curve point encoding",
+        ));
+    }
+    Ok(point)
 }
 
 /// Write a Pallas affine curve point (`EpAffine`) as 32 compressed bytes.
@@ -76,11 +152,22 @@ pub(crate) fn write_ep_affine<W: Write>(mut writer: W, point: &EpAffine) -> io::
 }
 
 /// Read a Vesta affine curve point (`EqAffine`) from 32 compressed bytes.
+///
+/// Rejects any encoding that does not round-trip, as
+/// [`read_ep_affine`] does.
 pub(crate) fn read_eq_affine<R: Read>(mut reader: R) -> io::Result<EqAffine> {
     let mut bytes = [0u8; 32];
     reader.read_exact(&mut bytes)?;
-    Option::from(EqAffine::from_bytes(&bytes))
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid curve point encoding"))
+    let point: EqAffine = Option::from(EqAffine::from_bytes(&bytes)).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "invalid curve point encoding")
+    })?;
+    if point.to_bytes() != bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "non-canonical curve point encoding",
+        ));
+    }
+    Ok(point)
 }
 
 /// Write a Vesta affine curve point (`EqAffine`) as 32 compressed bytes.
@@ -89,17 +176,26 @@ pub(crate) fn write_eq_affine<W: Write>(mut writer: W, point: &EqAffine) -> io::
 }
 
 /// Read a RedPallas action verification key from 32 bytes.
+///
+/// Rejects any encoding that does not round-trip.
 pub(crate) fn read_action_vk<R: Read>(
     mut reader: R,
 ) -> io::Result<reddsa::VerificationKey<reddsa::ActionAuth>> {
     let mut bytes = [0u8; 32];
     reader.read_exact(&mut bytes)?;
-    reddsa::VerificationKey::<reddsa::ActionAuth>::try_from(bytes).map_err(|_err| {
+    let key = reddsa::VerificationKey::<reddsa::ActionAuth>::try_from(bytes).map_err(|_err| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             "invalid action verification key",
         )
-    })
+    })?;
+    if <[u8; 32]>::from(key) != bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "non-canonical action verification key",
+        ));
+    }
+    Ok(key)
 }
 
 /// Write a RedPallas action verification key as 32 bytes.
@@ -111,13 +207,29 @@ pub(crate) fn write_action_vk<W: Write>(
     writer.write_all(&bytes)
 }
 
+/// Read the 64 bytes `R || s` of a RedPallas signature.
+///
+/// Rejects any encoding in which `R` is not a canonical Pallas point or `s`
+/// is not a canonical scalar, so each signature has exactly one accepted byte
+/// string.
+fn read_sig_bytes<R: Read>(mut reader: R) -> io::Result<[u8; 64]> {
+    let commitment = read_ep_affine(&mut reader)?;
+    let response = read_fq(&mut reader)?;
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&commitment.to_bytes());
+    bytes[32..].copy_from_slice(&response.to_repr());
+    Ok(bytes)
+}
+
 /// Read a RedPallas action signature from 64 bytes.
+///
+/// Rejects non-canonical encodings.
 pub(crate) fn read_action_sig<R: Read>(
-    mut reader: R,
+    reader: R,
 ) -> io::Result<reddsa::Signature<reddsa::ActionAuth>> {
-    let mut bytes = [0u8; 64];
-    reader.read_exact(&mut bytes)?;
-    Ok(reddsa::Signature::<reddsa::ActionAuth>::from(bytes))
+    Ok(reddsa::Signature::<reddsa::ActionAuth>::from(
+        read_sig_bytes(reader)?,
+    ))
 }
 
 /// Write a RedPallas action signature as 64 bytes.
@@ -129,12 +241,15 @@ pub(crate) fn write_action_sig<W: Write>(
     writer.write_all(&bytes)
 }
 
+/// Read a RedPallas binding signature from 64 bytes.
+///
+/// Rejects non-canonical encodings.
 pub(crate) fn read_binding_sig<R: Read>(
-    mut reader: R,
+    reader: R,
 ) -> io::Result<reddsa::Signature<reddsa::BindingAuth>> {
-    let mut bytes = [0u8; 64];
-    reader.read_exact(&mut bytes)?;
-    Ok(reddsa::Signature::<reddsa::BindingAuth>::from(bytes))
+    Ok(reddsa::Signature::<reddsa::BindingAuth>::from(
+        read_sig_bytes(reader)?,
+    ))
 }
 
 pub(crate) fn write_binding_sig<W: Write>(
@@ -0,0 +1,99 @@
+//! Versioned proof stamps, for storage and relay outside consensus.
+//!
+//! # Encoding
+//!
+//! | Name       | Format       | Description                              |
+//! | ---------- | ------------ | ---------------------------------------- |
+//! | `version`  | u8           | payload encoding                         |
+//! | `flags`    | u8           | low nibble required, high nibble ignored |
+//! | `nPayload` | compactsize  | payload length in bytes                  |
+//! | `payload`  | nPayload     | the stamp, in the given version          |
+//!
+//! Version 1 is the consensus encoding of a proof stamp. Bytes after it in the
+//! payload are reserved for fields a later version may add, and are skipped.
+
+use alloc::{vec, vec::Vec};
+
+use corez::io::{self, Read, Write};
+use derive_more::{Debug, Eq as TotalEq, PartialEq};
+
+use super::{ProofStamp, StampState as _};
+use crate::{constants::MAX_BLOCK_SIZE, serialization};
+
+/// A [`ProofStamp`] tagged with the version of its encoding.
+///
+/// The consensus encoding has no room for a version: a change to the proof
+/// system or to how tachygrams are published changes it outright. Wallets,
+/// provers, and relays that keep or pass stamps between themselves can wrap
+/// them in an envelope instead, so a decoder that meets a version it does not
+/// know can skip it rather than misread it.
+#[derive(Clone, Debug, PartialEq, TotalEq)]
+pub struct Envelope {
+    /// Encoding of the payload.
+    pub version: u8,
+    /// Flags qualifying the payload. A decoder must understand every flag set
+    /// in [`REQUIRED_FLAGS`](Self::REQUIRED_FLAGS), and may ignore the rest.
+    pub flags: u8,
+    /// The encoded stamp.
+    pub payload: Vec<u8>,
+}
+
+impl Envelope {
+    /// The version [`seal`](Self::seal) writes.
+    pub const VERSION: u8 = 1;
+
+    /// Flags a decoder must understand to open the payload. None are defined.
+    pub const REQUIRED_FLAGS: u8 = 0x0F;
+
+    /// Wrap a stamp in the current version.
+    pub fn seal(stamp: &ProofStamp) -> io::Result<Self> {
+        let mut payload = Vec::new();
+        stamp.write(&mut payload)?;
+        Ok(Self {
+            version: Self::VERSION,
+            flags: 0,
+            payload,
+        })
+    }
+
+    /// Unwrap the stamp, or `None` if the version or a required flag is one
+    /// this decoder does not know.
+    pub fn open(&self) -> io::Result<Option<ProofStamp>> {
+        if self.version != Self::VERSION || self.flags & Self::REQUIRED_FLAGS != 0 {
+            return Ok(None);
+        }
+        ProofStamp::read(self.payload.as_slice()).map(Some)
+    }
+
+    /// Read an envelope, whatever its version. See the module-level encoding
+    /// documentation.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut head = [0u8; 2];
+        reader.read_exact(&mut head)?;
+        let [version, flags] = head;
+
+        let len = usize::try_from(serialization::read_compactsize(&mut reader)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if len > MAX_BLOCK_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "envelope payload too large",
+            ));
+        }
+        let mut payload = vec![0u8; len];
+        reader.read_exact(&mut payload)?;
+
+        Ok(Self {
+            version,
+            flags,
+            payload,
+        })
+    }
+
+    /// Write the envelope. See the module-level encoding documentation.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&[self.version, self.flags])?;
+        serialization::write_len(&mut writer, self.payload.len())?;
+        writer.write_all(&self.payload)
+    }
+}
@@ -6,7 +6,15 @@ extern crate alloc;
 
 pub mod proof;
 
+mod envelope;
+mod partial;
+
 use alloc::{boxed::Box, collections::BTreeSet, vec, vec::Vec};
+use core::{
+    cmp::Eq as CoreTotalEq,
+    hash::{Hash, Hasher},
+    ops::ControlFlow,
+};
 
 use corez::io::{self, Read, Write};
 use derive_more::{Debug, Display, Eq as TotalEq, Error, Into, PartialEq};
@@ -14,24 +22,29 @@ use ff::PrimeField as _;
 use pasta_curves::Fp;
 use proof::{
     PROOF_SYSTEM,
-    stamp::{MergeStamp, OutputStamp, SpendStamp, StampHeader},
+    stamp::{MergeStamp, OutputStamp, SpendStamp, StampHeader, StampLift},
 };
 use ragu::{self, proof::PROOF_SIZE_COMPRESSED};
 use rand_core::{CryptoRng, RngCore};
 
 use crate::{
-    ActionSetPoly, Note, TachygramSetPoly, action,
-    bundle::{BundleState, StateByte},
+    ActionSetPoly, Note, TachygramDisjointness, TachygramSetPoly,
+    action::{self, Action},
+    bundle::{BundleState, StateByte, VerificationError, VerifyCoverageError, VerifyProofError},
+    constants::MAX_STAMP_TACHYGRAMS,
     digest::blake2b,
     effect,
     entropy::ActionRandomizer,
     keys::ProofAuthorizingKey,
     primitives::{ActionDigest, ActionDigestError, Anchor, Tachygram},
     serialization,
-    stamp::proof::{delegation, spend, spendable},
+    stamp::proof::{delegation, pool, spend, spendable},
     value,
 };
 
+pub use envelope::Envelope;
+pub use partial::{Partial, PartialError};
+
 /// Marker for a bundle that has not yet been proven.
 ///
 /// This is the initial state for a newly constructed bundle.
@@ -118,6 +131,9 @@ pub trait StampState: BundleState {
     fn write<W: Write>(&self, writer: W) -> io::Result<()>
     where
         Self: Sized;
+
+    /// Number of bytes [`write`](Self::write) produces.
+    fn encoded_len(&self) -> usize;
 }
 
 impl StampState for PointerStamp {
@@ -151,6 +167,10 @@ impl StampState for PointerStamp {
         }
         writer.write_all(&self.0)
     }
+
+    fn encoded_len(&self) -> usize {
+        self.0.len()
+    }
 }
 
 impl StampState for ProofStamp {
@@ -192,12 +212,16 @@ impl StampState for ProofStamp {
 
         let anchor = Anchor::read(&mut reader)?;
 
-        // `n_tachygrams` is attacker-controlled up to MAX_COMPACT_SIZE (2^25), so
-        // do not pre-allocate vector capacity. vector reads are ASSUMED to hit
-        // invalid data or EOF before significant problems occur.
-        // TODO: assert a reasonable maximum, to allow pre-allocation?
+        // `n_tachygrams` is attacker-controlled, so it is bounded by what a
+        // block could hold before anything is read against it.
         let n_tachygrams = usize::try_from(serialization::read_compactsize(&mut reader)?)
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if n_tachygrams > MAX_STAMP_TACHYGRAMS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "too many tachygrams",
+            ));
+        }
 
         let mut tachygrams: BTreeSet<Tachygram> = BTreeSet::new();
         for _ in 0..n_tachygrams {
@@ -259,6 +283,10 @@ impl StampState for ProofStamp {
         }
         writer.write_all(self.proof.serialize().as_ref())
     }
+
+    fn encoded_len(&self) -> usize {
+        Self::estimated_serialized_size(self.tachygrams.len())
+    }
 }
 
 /// Everything needed to produce a [`ProofStamp`].
@@ -313,6 +341,43 @@ impl Plan {
         }
     }
 
+    /// The descriptors of the actions the plan covers.
+    ///
+    /// A stamp proven from the plan covers exactly these, as a piece of a
+    /// [`Partial`] must.
+    #[must_use]
+    pub fn descriptors(&self) -> BTreeSet<action::Descriptor> {
+        self.spends
+            .iter()
+            .map(|spend| spend.0)
+            .chain(self.outputs.iter().map(|output| output.0))
+            .collect()
+    }
+
+    /// The work [`Plan::prove`] will do, for a wallet to warn before a large
+    /// prove or an aggregator to budget block assembly.
+    ///
+    /// A spend takes two PCD steps, [`spend::SpendBind`] and [`SpendStamp`],
+    /// and an output one; merging `n` leaves takes `n - 1` more. Each leaf and
+    /// merge is then rerandomized. Lifts are not counted, since the plan does
+    /// not know its spends' anchors: each adds one step and one
+    /// rerandomization. These are the steps
+    /// [`prove_observed`](Self::prove_observed) reports.
+    ///
+    /// There is no memory or time estimate. Both follow from the proof
+    /// system's circuit sizes, which it does not expose, and time from the
+    /// prover's hardware too; a wallet can time one step and scale by
+    /// `steps`.
+    #[must_use]
+    pub fn cost(&self) -> ProvingCost {
+        let leaves = self.spends.len() + self.outputs.len();
+        let merges = leaves.saturating_sub(1);
+        ProvingCost {
+            steps: 2 * self.spends.len() + self.outputs.len() + merges,
+            rerandomizations: leaves + merges,
+        }
+    }
+
     /// Prove a single [`ProofStamp`] for this plan.
     ///
     /// For each **spend**, uses [`spend::SpendBind`] to prepare PCD inputs,
@@ -320,13 +385,24 @@ impl Plan {
     ///
     /// For each **output**, runs [`OutputStamp`] with no PCD inputs.
     ///
-    /// Stamps are recursively merged via [`MergeStamp`] into a single stamp.
+    /// Stamps are merged pairwise via [`MergeStamp`], in a balanced tree,
+    /// into a single stamp.
     ///
     /// `spendbind_inputs` items must correspond to each planned spend, in
     /// order.
     ///
+    /// Each descriptor is first checked against its witness with
+    /// [`action::Descriptor::check_spend`] or
+    /// [`action::Descriptor::check_output`].
+    ///
+    /// Every spend's lineage must end at the plan anchor. To spend notes whose
+    /// lineages end at older anchors, use [`Plan::prove_lifted`].
+    ///
+    /// All randomness, including each proof's rerandomization, is drawn from
+    /// `rng`. A seeded `rng` reproduces the stamp byte for byte, as test
+    /// vectors need.
+    ///
     /// TODO: nf_next parameter may need to come back
-    /// TODO: provide a way to lift spend stamps when necessary to merge
     pub fn prove<RNG: RngCore + CryptoRng>(
         self,
         rng: &mut RNG,
@@ -335,6 +411,51 @@ impl Plan {
             ragu::Pcd<delegation::NullifierHeader>,
             ragu::Pcd<spendable::SpendableHeader>,
         )>,
+    ) -> Result<ProofStamp, ProveError> {
+        self.prove_lifted(rng, pak, spendbind_inputs, Vec::new())
+    }
+
+    /// Prove a single [`ProofStamp`] for this plan, where spends may reference
+    /// different anchors.
+    ///
+    /// As [`Plan::prove`], except that a spend whose lineage ends at an anchor
+    /// other than the plan anchor is lifted via [`StampLift`] before merging.
+    /// `lifts` must contain, for each such anchor, an [`pool::AnchorChain`]
+    /// from that anchor to the plan anchor. The plan anchor is the bundle
+    /// anchor, so it should be the latest anchor of any action.
+    pub fn prove_lifted<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        pak: &ProofAuthorizingKey,
+        spendbind_inputs: Vec<(
+            ragu::Pcd<delegation::NullifierHeader>,
+            ragu::Pcd<spendable::SpendableHeader>,
+        )>,
+        lifts: Vec<ragu::Pcd<pool::AnchorChain>>,
+    ) -> Result<ProofStamp, ProveError> {
+        self.prove_observed(rng, pak, spendbind_inputs, lifts, |_progress| {
+            ControlFlow::Continue(())
+        })
+    }
+
+    /// As [`Plan::prove_lifted`], reporting to `observe` after each step.
+    ///
+    /// Proving a large plan takes many seconds. A step is one PCD step, as
+    /// counted by [`Plan::cost`], plus one for each spend whose lineage must
+    /// be lifted to the plan anchor. Returning [`ControlFlow::Break`] from
+    /// `observe` stops proving before the next step begins, with
+    /// [`ProveError::Cancelled`]. The last report comes with the stamp already
+    /// proven, so breaking then does not discard it.
+    pub fn prove_observed<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        pak: &ProofAuthorizingKey,
+        spendbind_inputs: Vec<(
+            ragu::Pcd<delegation::NullifierHeader>,
+            ragu::Pcd<spendable::SpendableHeader>,
+        )>,
+        lifts: Vec<ragu::Pcd<pool::AnchorChain>>,
+        mut observe: impl FnMut(Progress) -> ControlFlow<()>,
     ) -> Result<ProofStamp, ProveError> {
         // Each entry pairs leaf stamp components with the descriptor and
         // action digest of its covered action; merges concatenate both
@@ -347,6 +468,32 @@ impl Plan {
             return Err(ProveError::SpendableMismatch);
         }
 
+        let lifted = spendbind_inputs
+            .iter()
+            .filter(|inputs| inputs.1.data().2 != self.anchor)
+            .count();
+        let mut progress = Progress {
+            proven: 0,
+            total: self.cost().steps + lifted,
+        };
+        let mut step = || {
+            progress.proven += 1;
+            let done = progress.proven >= progress.total;
+            match observe(progress) {
+                ControlFlow::Break(()) if !done => Err(ProveError::Cancelled),
+                ControlFlow::Break(()) | ControlFlow::Continue(()) => Ok(()),
+            }
+        };
+
+        for &(desc, alpha, note, rcv) in &self.spends {
+            desc.check_spend(&pak.ak, &alpha, &note, rcv)
+                .map_err(ProveError::WitnessMismatch)?;
+        }
+        for &(desc, alpha, note, rcv) in &self.outputs {
+            desc.check_output(&alpha, &note, rcv)
+                .map_err(ProveError::WitnessMismatch)?;
+        }
+
         for ((desc, alpha, note, rcv), (nf_pcd, spendable_pcd)) in
             self.spends.into_iter().zip(spendbind_inputs)
         {
@@ -359,16 +506,37 @@ impl Plan {
                     ragu::Proof::trivial().carry::<()>(()),
                 )
                 .map_err(ProveError::ProofFailed)?;
+            step()?;
 
             // SpendStamp: bind the live pair to the derived range and publish.
-            let (tachygrams, anchor, proof) =
+            let (tachygrams, spend_anchor, spend_proof) =
                 ProofStamp::prove_spend(rng, bind_pcd, nf_pcd).map_err(ProveError::ProofFailed)?;
+            step()?;
 
             let digest = desc.digest().map_err(ProveError::ActionDigest)?;
-            entries.push((
-                BTreeSet::from_iter([desc]),
+            let mut components = (
                 BTreeSet::from_iter([digest]),
                 tachygrams,
+                spend_anchor,
+                spend_proof,
+            );
+
+            // StampLift: advance a spend stamped at an older anchor.
+            if spend_anchor != self.anchor {
+                let chain = lifts
+                    .iter()
+                    .find(|segment| *segment.data() == (spend_anchor, self.anchor))
+                    .ok_or(ProveError::AnchorUnreachable)?;
+                components = ProofStamp::prove_lift(rng, components, chain.clone())
+                    .map_err(ProveError::ProofFailed)?;
+                step()?;
+            }
+
+            let (digests, lifted_tachygrams, anchor, proof) = components;
+            entries.push((
+                BTreeSet::from_iter([desc]),
+                digests,
+                lifted_tachygrams,
                 anchor,
                 proof,
             ));
@@ -387,15 +555,14 @@ impl Plan {
                 anchor,
                 proof,
             ));
+            step()?;
         }
 
-        let (descriptors, _digests, tachygrams, anchor, proof) = entries
-            .into_iter()
-            .map(Ok::<_, ProveError>)
-            .reduce(|acc, next| {
-                let (left_desc, left_digests, left_tachygrams, left_anchor, left_proof) = acc?;
+        let (descriptors, _digests, tachygrams, anchor, proof) =
+            merge_balanced(entries, |left, right| {
+                let (left_desc, left_digests, left_tachygrams, left_anchor, left_proof) = left;
                 let (right_desc, right_digests, right_tachygrams, right_anchor, right_proof) =
-                    next?;
+                    right;
 
                 let (merged_digests, merged_tachygrams, merged_anchor, merged_proof) =
                     ProofStamp::prove_merge(
@@ -406,6 +573,7 @@ impl Plan {
                     .map_err(ProveError::MergeFailed)?;
 
                 let merged_descs = left_desc.union(&right_desc).copied().collect();
+                step()?;
 
                 Ok((
                     merged_descs,
@@ -414,8 +582,7 @@ impl Plan {
                     merged_anchor,
                     merged_proof,
                 ))
-            })
-            .ok_or(ProveError::NoActions)??;
+            })?;
 
         let coverage = blake2b::action_descriptor_digest(&Vec::<[u8; 64]>::from_iter(descriptors));
 
@@ -428,6 +595,24 @@ impl Plan {
     }
 }
 
+/// The work of proving a [`Plan`]. See [`Plan::cost`].
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub struct ProvingCost {
+    /// PCD steps, each a seed or a fuse.
+    pub steps: usize,
+    /// Proof rerandomizations.
+    pub rerandomizations: usize,
+}
+
+/// How far [`Plan::prove_observed`] has got.
+#[derive(Clone, Copy, Debug, PartialEq, TotalEq)]
+pub struct Progress {
+    /// Steps finished.
+    pub proven: usize,
+    /// Steps in all.
+    pub total: usize,
+}
+
 /// Errors that can occur while proving a stamp.
 #[derive(Debug, Display, Error)]
 #[non_exhaustive]
@@ -448,6 +633,19 @@ pub enum ProveError {
     /// Number of spendable PCDs doesn't match number of spends.
     #[display("spendable PCD count mismatch")]
     SpendableMismatch,
+    /// No anchor chain lifts a spend's anchor to the plan anchor, or joins
+    /// the anchors of two stamps being merged.
+    #[display("no anchor chain joins the anchors to be combined")]
+    AnchorUnreachable,
+    /// A planned descriptor does not match its witness.
+    #[display("action witness mismatch: {_0}")]
+    WitnessMismatch(action::WitnessMismatch),
+    /// Proving was stopped by its observer.
+    #[display("proving cancelled")]
+    Cancelled,
+    /// Both stamps being merged publish this tachygram.
+    #[display("stamps share tachygram {_0:?}")]
+    TachygramCollision(#[error(not(source))] Tachygram),
 }
 
 /// A stamp carrying tachygrams, anchor, and a proof for specific actions.
@@ -467,6 +665,18 @@ pub struct ProofStamp {
     pub anchor: Anchor,
 
     /// Tachygrams (nullifiers and note commitments) for data availability.
+    ///
+    /// Each output contributes its note commitment and each spend its live
+    /// nullifier pair, so there may be more tachygrams than actions. Every
+    /// tachygram is committed in the proof header: a tachygram that no action
+    /// step derived cannot be attached, since an unbacked note commitment
+    /// would mint value.
+    ///
+    /// Being a set, tachygrams are always in canonical order, and
+    /// [`read`](StampState::read) rejects any other order or a repeat. A
+    /// merge publishes the union of its parts, and [`merge`](Self::merge)
+    /// refuses parts that overlap. The merge proof itself shows the parts
+    /// are disjoint, so no proof commits to a repeated tachygram.
     pub tachygrams: BTreeSet<Tachygram>,
 
     /// The Ragu proof bytes.
@@ -474,6 +684,24 @@ pub struct ProofStamp {
     pub proof: Box<ragu::Proof>,
 }
 
+/// Stamps are equal when their [`stamp_digest`](StampState::stamp_digest)s
+/// are: the same coverage, anchor, tachygrams, and proof bytes. Relays can
+/// deduplicate stamps, and caches key them, without comparing proofs field
+/// by field.
+impl PartialEq for ProofStamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.stamp_digest() == other.stamp_digest()
+    }
+}
+
+impl CoreTotalEq for ProofStamp {}
+
+impl Hash for ProofStamp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.stamp_digest().hash(state);
+    }
+}
+
 /// Stamp components threaded through the merge fold: the covered actions'
 /// digests, the tachygrams, the shared anchor, and the proof.
 type StampComponents = (
@@ -484,6 +712,20 @@ type StampComponents = (
 );
 
 impl ProofStamp {
+    /// Encoded size of a stamp publishing `tachygrams` tachygrams.
+    ///
+    /// The proof has a fixed compressed size, so this is exact before the
+    /// stamp is proven. Each spend publishes two tachygrams and each output
+    /// one; a merged stamp publishes the union of its parts'.
+    #[must_use]
+    pub fn estimated_serialized_size(tachygrams: usize) -> usize {
+        // coverage, anchor, tachygrams, proof
+        32 + 32
+            + serialization::compactsize_len(tachygrams)
+            + 32 * tachygrams
+            + PROOF_SIZE_COMPRESSED
+    }
+
     /// Proves a single output action, returning the stamp components
     /// `(tachygrams, anchor, proof)`.
     ///
@@ -528,6 +770,39 @@ impl ProofStamp {
         Ok((tachygrams, anchor, Box::new(rerand.proof().clone())))
     }
 
+    /// Lifts a stamp to a later anchor over an [`pool::AnchorChain`],
+    /// returning the lifted stamp components
+    /// `(digests, tachygrams, anchor, proof)`.
+    ///
+    /// The chain must start at the stamp's anchor; the lifted anchor is the
+    /// chain's end. Digests and tachygrams are unchanged.
+    pub fn prove_lift<RNG: RngCore + CryptoRng>(
+        rng: &mut RNG,
+        (digests, tachygrams, anchor, proof): StampComponents,
+        chain: ragu::Pcd<pool::AnchorChain>,
+    ) -> Result<StampComponents, ragu::Error> {
+        let stamp_pcd = proof.carry::<StampHeader>((
+            digests.iter().copied().collect::<ActionSetPoly>().commit(),
+            tachygrams
+                .iter()
+                .copied()
+                .collect::<TachygramSetPoly>()
+                .commit(),
+            anchor,
+        ));
+
+        let (pcd, ()) = PROOF_SYSTEM.fuse(rng, StampLift, (), stamp_pcd, chain)?;
+        let lifted_anchor = pcd.data().2;
+        let rerand = PROOF_SYSTEM.rerandomize(pcd, rng)?;
+
+        Ok((
+            digests,
+            tachygrams,
+            lifted_anchor,
+            Box::new(rerand.proof().clone()),
+        ))
+    }
+
     /// Proves the merge of two stamps, returning the merged stamp
     /// components `(digests, tachygrams, anchor, proof)`.
     ///
@@ -539,6 +814,9 @@ impl ProofStamp {
     /// caller and are never stored on the stamp; the merged (concatenated)
     /// digest list is returned so a fold can carry it forward without
     /// re-deriving.
+    ///
+    /// The step also proves the two tachygram sets disjoint, so overlapping
+    /// sets fail to prove; [`merge`](Self::merge) refuses them before proving.
     pub fn prove_merge<RNG: RngCore + CryptoRng>(
         rng: &mut RNG,
         (left_digests, left_tachygrams, left_anchor, left_proof): StampComponents,
@@ -575,6 +853,10 @@ impl ProofStamp {
             left_digests.union(&right_digests).copied().collect();
         let tachygrams: BTreeSet<Tachygram> =
             left_tachygrams.union(&right_tachygrams).copied().collect();
+        let disjointness = TachygramDisjointness::new(
+            left_tachygrams.iter().copied(),
+            right_tachygrams.iter().copied(),
+        );
 
         let (pcd, ()) = PROOF_SYSTEM.fuse(
             rng,
@@ -586,6 +868,7 @@ impl ProofStamp {
                     TachygramSetPoly::from_iter(tachygrams.clone()),
                 ),
                 (right_acts_poly, right_tg_poly),
+                disjointness,
             ),
             left_pcd,
             right_pcd,
@@ -601,19 +884,104 @@ impl ProofStamp {
         ))
     }
 
+    /// Lifts the stamp to a later anchor over `chain`, which must start at
+    /// the stamp's anchor.
+    ///
+    /// `covered` are the descriptors of the actions the stamp covers; the
+    /// coverage and tachygrams are unchanged.
+    pub fn lift<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        covered: &BTreeSet<action::Descriptor>,
+        chain: ragu::Pcd<pool::AnchorChain>,
+    ) -> Result<Self, ProveError> {
+        let digests = covered
+            .iter()
+            .map(action::Descriptor::digest)
+            .collect::<Result<BTreeSet<ActionDigest>, ActionDigestError>>()
+            .map_err(ProveError::ActionDigest)?;
+
+        let (_digests, tachygrams, anchor, proof) = Self::prove_lift(
+            rng,
+            (digests, self.tachygrams, self.anchor, self.proof),
+            chain,
+        )
+        .map_err(ProveError::ProofFailed)?;
+
+        Ok(Self {
+            coverage: self.coverage,
+            anchor,
+            tachygrams,
+            proof,
+        })
+    }
+
+    /// Rerandomizes the proof, so its bytes no longer match the prover's.
+    ///
+    /// A relay can rerandomize a stamp before passing it on, so it cannot be
+    /// linked to the prover by its proof bytes. `covered` are the descriptors
+    /// of the actions the stamp covers; the coverage, anchor, and tachygrams
+    /// are unchanged.
+    ///
+    /// The new proof changes the stamp's digest, and so the wtxid of the
+    /// transaction carrying it: an aggregate's adjuncts would no longer point
+    /// to it.
+    pub fn rerandomize<RNG: RngCore + CryptoRng>(
+        self,
+        rng: &mut RNG,
+        covered: &BTreeSet<action::Descriptor>,
+    ) -> Result<Self, ProveError> {
+        let action_set = covered
+            .iter()
+            .map(action::Descriptor::digest)
+            .collect::<Result<ActionSetPoly, ActionDigestError>>()
+            .map_err(ProveError::ActionDigest)?;
+        let tachygram_set = self
+            .tachygrams
+            .iter()
+            .copied()
+            .collect::<TachygramSetPoly>();
+
+        let pcd = self.proof.carry::<StampHeader>((
+            action_set.commit(),
+            tachygram_set.commit(),
+            self.anchor,
+        ));
+        let rerand = PROOF_SYSTEM
+            .rerandomize(pcd, rng)
+            .map_err(ProveError::ProofFailed)?;
+
+        Ok(Self {
+            proof: Box::new(rerand.proof().clone()),
+            ..self
+        })
+    }
+
     /// Merges two stamps into one covering stamp.
     ///
     /// Each side pairs a stamp with the descriptors of its covered actions.
     /// The action digests for the merge proof and the merged
-    /// `covered_actions` are both derived from the descriptor lists.
+    /// `covered_actions` are both derived from the descriptor lists, which
+    /// are only borrowed, so a caller folding many stamps can keep extending
+    /// one set.
     ///
     /// TODO: confirm desc list against stamp? it's forbidden by the proof
     /// system, but we might want to fail early.
+    ///
+    /// # Errors
+    ///
+    /// [`ProveError::TachygramCollision`] if the stamps share a tachygram,
+    /// checked before proving; otherwise [`ProveError::ActionDigest`] or
+    /// [`ProveError::MergeFailed`].
     pub fn merge<RNG: RngCore + CryptoRng>(
         rng: &mut RNG,
-        (left_stamp, left_desc): (Self, BTreeSet<action::Descriptor>),
-        (right_stamp, right_desc): (Self, BTreeSet<action::Descriptor>),
+        (left_stamp, left_desc): (Self, &BTreeSet<action::Descriptor>),
+        (right_stamp, right_desc): (Self, &BTreeSet<action::Descriptor>),
     ) -> Result<Self, ProveError> {
+        if let Some(&tachygram) = left_stamp.collisions(&right_stamp).first() {
+            return Err(ProveError::TachygramCollision(tachygram));
+        }
+
         let left_actions_digest = left_desc
             .iter()
             .map(action::Descriptor::digest)
@@ -644,7 +1012,7 @@ impl ProofStamp {
 
         let coverage = blake2b::action_descriptor_digest(
             &left_desc
-                .union(&right_desc)
+                .union(right_desc)
                 .copied()
                 .collect::<Vec<[u8; 64]>>(),
         );
@@ -657,6 +1025,72 @@ impl ProofStamp {
         })
     }
 
+    /// Merges any number of stamps, each paired with the descriptors of its
+    /// covered actions, into one.
+    ///
+    /// Merges run in a balanced tree, pairing neighbours level by level, so
+    /// the merged proof's recursion depth grows with the logarithm of the
+    /// number of stamps rather than the number itself. All stamps must share
+    /// an anchor: see [`merge_lifting`](Self::merge_lifting).
+    ///
+    /// # Errors
+    ///
+    /// [`ProveError::NoActions`] if there are no stamps; otherwise as
+    /// [`merge`](Self::merge).
+    pub fn merge_all<RNG: RngCore + CryptoRng>(
+        rng: &mut RNG,
+        stamps: impl IntoIterator<Item = (Self, BTreeSet<action::Descriptor>)>,
+    ) -> Result<Self, ProveError> {
+        let (stamp, _descriptors) = merge_balanced(stamps.into_iter().collect(), |left, right| {
+            let merged = Self::merge(rng, (left.0, &left.1), (right.0, &right.1))?;
+            let mut descriptors = left.1;
+            descriptors.extend(right.1);
+            Ok((merged, descriptors))
+        })?;
+        Ok(stamp)
+    }
+
+    /// Merges two stamps at the later of their anchors.
+    ///
+    /// Stamps at the same anchor merge directly. Otherwise whichever is older
+    /// is first [lifted](Self::lift) over the segment in `lifts` that joins
+    /// the two anchors, so callers need not work out which side to lift, or
+    /// over what.
+    ///
+    /// # Errors
+    ///
+    /// [`ProveError::AnchorUnreachable`] if the anchors differ and no segment
+    /// in `lifts` joins them; otherwise as [`lift`](Self::lift) and
+    /// [`merge`](Self::merge).
+    pub fn merge_lifting<RNG: RngCore + CryptoRng>(
+        rng: &mut RNG,
+        (mut left_stamp, left_desc): (Self, &BTreeSet<action::Descriptor>),
+        (mut right_stamp, right_desc): (Self, &BTreeSet<action::Descriptor>),
+        lifts: &[ragu::Pcd<pool::AnchorChain>],
+    ) -> Result<Self, ProveError> {
+        // Lifting keeps the tachygrams, so refuse an overlap before proving one.
+        if let Some(&tachygram) = left_stamp.collisions(&right_stamp).first() {
+            return Err(ProveError::TachygramCollision(tachygram));
+        }
+        if left_stamp.anchor != right_stamp.anchor {
+            let segment = |start: Anchor, end: Anchor| {
+                lifts
+                    .iter()
+                    .find(|chain| *chain.data() == (start, end))
+                    .cloned()
+            };
+            if let Some(chain) = segment(left_stamp.anchor, right_stamp.anchor) {
+                left_stamp = left_stamp.lift(rng, left_desc, chain)?;
+            } else if let Some(chain) = segment(right_stamp.anchor, left_stamp.anchor) {
+                right_stamp = right_stamp.lift(rng, right_desc, chain)?;
+            } else {
+                return Err(ProveError::AnchorUnreachable);
+            }
+        }
+
+        Self::merge(rng, (left_stamp, left_desc), (right_stamp, right_desc))
+    }
+
     /// Confirm `hStampActionsTachyon` represents the given action descriptors.
     ///
     /// # Soundness
@@ -672,6 +1106,19 @@ impl ProofStamp {
         blake2b::action_descriptor_digest(&desc_bytes) == self.coverage
     }
 
+    /// Tachygrams this stamp shares with `other`.
+    ///
+    /// Both sets are sorted, so this is a single merge pass. Stamps that
+    /// collide cannot be merged, and must not land in the same block: a
+    /// shared tachygram is a repeated nullifier or note commitment.
+    #[must_use]
+    pub fn collisions(&self, other: &Self) -> BTreeSet<Tachygram> {
+        self.tachygrams
+            .intersection(&other.tachygrams)
+            .copied()
+            .collect()
+    }
+
     /// Reconstruct the PCD header and verify the proof. Call
     /// [`ProofStamp::is_covering`] first to cheaply predict a mismatch.
     ///
@@ -683,8 +1130,16 @@ impl ProofStamp {
         rng: &mut RNG,
         action_digests: impl IntoIterator<Item = ActionDigest>,
     ) -> Result<bool, ragu::Error> {
-        let action_set = action_digests.into_iter().collect::<ActionSetPoly>();
+        self.verify_proof_for(rng, &action_digests.into_iter().collect())
+    }
 
+    /// As [`verify_proof`](Self::verify_proof), against an action set
+    /// already built.
+    pub(crate) fn verify_proof_for<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        action_set: &ActionSetPoly,
+    ) -> Result<bool, ragu::Error> {
         let tachygram_set = self
             .tachygrams
             .iter()
@@ -699,6 +1154,106 @@ impl ProofStamp {
 
         PROOF_SYSTEM.verify(&pcd, rng)
     }
+
+    /// Verify the stamp as covering exactly `actions`, as an autonome's does.
+    ///
+    /// Only the stamp is checked: signatures and the anchor's age are not.
+    /// See [`Bundle::verify_for_block`](crate::Bundle::verify_for_block).
+    pub fn verify<RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        actions: &[Action],
+    ) -> Result<(), VerificationError> {
+        self.verify_covering(rng, [actions])
+    }
+
+    /// Verify the stamp as covering exactly the actions of `bundles`, each
+    /// once, as an aggregate's does.
+    ///
+    /// Actions are indexed across `bundles` in order, as in
+    /// [`VerifyProofError::ActionDigest`].
+    pub fn verify_covering<'actions, RNG: RngCore + CryptoRng>(
+        &self,
+        rng: &mut RNG,
+        bundles: impl IntoIterator<Item = &'actions [Action]>,
+    ) -> Result<(), VerificationError> {
+        let actions: Vec<Action> = bundles.into_iter().flatten().copied().collect();
+
+        let descriptors: BTreeSet<action::Descriptor> =
+            actions.iter().map(Action::descriptor).collect();
+        if descriptors.len() != actions.len() {
+            return Err(VerificationError::Coverage(
+                VerifyCoverageError::DuplicateActions,
+            ));
+        }
+        if !self.is_covering(descriptors) {
+            return Err(VerificationError::Coverage(
+                VerifyCoverageError::StampActionsMismatch,
+            ));
+        }
+
+        let digests = actions
+            .iter()
+            .enumerate()
+            .map(|(index, action)| {
+                action
+                    .digest()
+                    .map_err(|err| VerifyProofError::ActionDigest(index, err))
+            })
+            .collect::<Result<Vec<ActionDigest>, VerifyProofError>>()
+            .map_err(VerificationError::Proof)?;
+
+        if self
+            .verify_proof(rng, digests)
+            .map_err(|err| VerificationError::Proof(VerifyProofError::ProofSystem(err)))?
+        {
+            Ok(())
+        } else {
+            Err(VerificationError::Disproved)
+        }
+    }
+}
+
+/// Reduce `items` to one by merging neighbours, level by level, so that the
+/// merge tree is balanced.
+///
+/// An odd item out at any level is carried up to the next unmerged.
+fn merge_balanced<T>(
+    mut level: Vec<T>,
+    mut merge: impl FnMut(T, T) -> Result<T, ProveError>,
+) -> Result<T, ProveError> {
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut items = level.into_iter();
+        while let Some(left) = items.next() {
+            next.push(match items.next() {
+                Some(right) => merge(left, right)?,
+                None => left,
+            });
+        }
+        level = next;
+    }
+    level.pop().ok_or(ProveError::NoActions)
+}
+
+/// Tachygrams published by more than one of `stamps`.
+///
+/// For a mempool or aggregator screening a candidate set of stamps before
+/// merging them, in one pass over their tachygrams. A stamp never repeats a
+/// tachygram of its own.
+pub fn duplicate_tachygrams<'stamp>(
+    stamps: impl IntoIterator<Item = &'stamp ProofStamp>,
+) -> BTreeSet<Tachygram> {
+    let mut seen = BTreeSet::new();
+    let mut duplicates = BTreeSet::new();
+    for stamp in stamps {
+        for &tachygram in &stamp.tachygrams {
+            if !seen.insert(tachygram) {
+                duplicates.insert(tachygram);
+            }
+        }
+    }
+    duplicates
 }
 
 #[cfg(test)]
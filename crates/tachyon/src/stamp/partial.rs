@@ -0,0 +1,173 @@
+//! Stamps proven in pieces, for assembly once every piece is in.
+//!
+//! # Encoding
+//!
+//! | Name           | Format                   | Description                      |
+//! | -------------- | ------------------------ | -------------------------------- |
+//! | `nPieces`      | compactsize              | number of pieces                 |
+//! | `vPieces`      | nPieces * piece          | pieces, in the order added       |
+//!
+//! Each piece is:
+//!
+//! | Name           | Format                   | Description                      |
+//! | -------------- | ------------------------ | -------------------------------- |
+//! | `nCovered`     | compactsize              | number of covered actions        |
+//! | `vCovered`     | 64 * nCovered            | (cv, rk), in descriptor order    |
+//! | `stamp`        | proof stamp              | as in a proof-stamped bundle     |
+
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use corez::io::{self, Read, Write};
+use derive_more::{Debug, Display, Eq as TotalEq, Error, PartialEq};
+use rand_core::{CryptoRng, RngCore};
+
+use super::{ProofStamp, ProveError, StampState as _};
+use crate::{action, primitives::Tachygram, serialization};
+
+/// Errors from adding a piece to a [`Partial`].
+#[derive(Clone, Copy, Debug, Display, Error, PartialEq, TotalEq)]
+#[non_exhaustive]
+pub enum PartialError {
+    /// The piece's stamp does not cover the actions given with it.
+    #[display("stamp does not cover the given actions")]
+    NotCovering,
+    /// The piece covers an action another piece already does.
+    #[display("action already covered")]
+    SharedAction,
+    /// The piece publishes a tachygram another piece already does.
+    #[display("tachygram {_0:?} already published")]
+    SharedTachygram(#[error(not(source))] Tachygram),
+    /// The piece's anchor differs from the other pieces'.
+    #[display("anchor differs from the other pieces")]
+    AnchorMismatch,
+}
+
+/// A stamp proven in pieces, not yet assembled.
+///
+/// Proving a stamp need not happen in one pass. Each party proves the actions
+/// it holds witnesses for as soon as it can, with a
+/// [`Plan`](super::Plan) covering just those, and passes on the stamp with
+/// the plan's [`descriptors`](super::Plan::descriptors). An assembler
+/// collects the pieces with [`add`](Self::add) or [`combine`](Self::combine)
+/// and merges them with [`finish`](Self::finish).
+///
+/// Pieces are checked as they arrive, so one that could not merge is turned
+/// away before any merge is proven. Every piece must be at the same anchor.
+#[derive(Clone, Debug, Default)]
+pub struct Partial {
+    pieces: Vec<(ProofStamp, BTreeSet<action::Descriptor>)>,
+}
+
+impl Partial {
+    /// Start with no pieces.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    /// Add a piece: a stamp, and the descriptors of the actions it covers.
+    ///
+    /// # Errors
+    ///
+    /// Rejects the piece, leaving the others as they were, if its stamp does
+    /// not cover `covered`, or if it shares an action, a tachygram, or not
+    /// its anchor with a piece already added.
+    pub fn add(
+        &mut self,
+        stamp: ProofStamp,
+        covered: BTreeSet<action::Descriptor>,
+    ) -> Result<(), PartialError> {
+        if !stamp.is_covering(covered.iter().copied()) {
+            return Err(PartialError::NotCovering);
+        }
+        for piece in &self.pieces {
+            if piece.0.anchor != stamp.anchor {
+                return Err(PartialError::AnchorMismatch);
+            }
+            if !piece.1.is_disjoint(&covered) {
+                return Err(PartialError::SharedAction);
+            }
+            if let Some(&tachygram) = piece.0.collisions(&stamp).first() {
+                return Err(PartialError::SharedTachygram(tachygram));
+            }
+        }
+        self.pieces.push((stamp, covered));
+        Ok(())
+    }
+
+    /// Add every piece of `other`, as collected elsewhere.
+    ///
+    /// # Errors
+    ///
+    /// As [`add`](Self::add), for the first piece rejected; the pieces of
+    /// `other` before it are kept.
+    pub fn combine(&mut self, other: Self) -> Result<(), PartialError> {
+        for (stamp, covered) in other.pieces {
+            self.add(stamp, covered)?;
+        }
+        Ok(())
+    }
+
+    /// Number of pieces collected.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    /// Whether no piece has been collected.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// The actions the pieces cover between them.
+    #[must_use]
+    pub fn covered(&self) -> BTreeSet<action::Descriptor> {
+        self.pieces
+            .iter()
+            .flat_map(|piece| piece.1.iter().copied())
+            .collect()
+    }
+
+    /// Merge the pieces into one stamp. See [`ProofStamp::merge_all`].
+    pub fn finish<RNG: RngCore + CryptoRng>(self, rng: &mut RNG) -> Result<ProofStamp, ProveError> {
+        ProofStamp::merge_all(rng, self.pieces)
+    }
+
+    /// Read the pieces. See the module-level encoding documentation.
+    ///
+    /// Each piece is checked as by [`add`](Self::add).
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        // lengths are attacker-controlled, so do not pre-allocate.
+        let mut partial = Self::new();
+        for _ in 0..serialization::read_compactsize(&mut reader)? {
+            let mut covered = BTreeSet::new();
+            for _ in 0..serialization::read_compactsize(&mut reader)? {
+                if !covered.insert(action::Descriptor::read(&mut reader)?) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "duplicate covered action",
+                    ));
+                }
+            }
+            let stamp = ProofStamp::read(&mut reader)?;
+            partial
+                .add(stamp, covered)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+        Ok(partial)
+    }
+
+    /// Write the pieces. See the module-level encoding documentation.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_len(&mut writer, self.pieces.len())?;
+        for piece in &self.pieces {
+            serialization::write_len(&mut writer, piece.1.len())?;
+            for desc in &piece.1 {
+                desc.write(&mut writer)?;
+            }
+            piece.0.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
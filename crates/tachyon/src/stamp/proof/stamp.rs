@@ -4,21 +4,22 @@ extern crate alloc;
 
 use alloc::{vec, vec::Vec};
 
+use ff::Field as _;
 use pasta_curves::{Ep, Eq, Fp, Fq};
 use ragu::{
-    Cycle as _, FixedGenerators as _, Header, Index, Pasta, Step, Suffix,
+    Cycle as _, FixedGenerators as _, Header, Index, Pasta, Polynomial, Step, Suffix,
     constraint::{enforce_equal_point, enforce_nonzero, enforce_zero},
 };
 
 use super::{delegation::NullifierHeader, pool::AnchorChain, spend::SpendHeader};
 use crate::{
-    ActionSetPoly, TachygramSetPoly,
+    ActionSetPoly, TachygramDisjointness, TachygramSetPoly,
     constants::MAX_MONEY,
     entropy::ActionRandomizer,
     keys::private,
     note::{Note, Nullifier},
     primitives::{ActionDigest, ActionSetCommit, Anchor, TachygramSetCommit, effect},
-    relations::enforce::enforce_poly_product,
+    relations::enforce::{enforce_poly_product, enforce_shifted_combination},
     value,
 };
 
@@ -210,6 +211,10 @@ impl Step for SpendStamp {
 }
 
 /// Universal merge — transaction assembly and aggregation.
+///
+/// The merged sets are the products of their halves, and the two tachygram
+/// sets are proven disjoint by a [`TachygramDisjointness`] witness, so a
+/// merged stamp never publishes a tachygram twice.
 #[derive(Debug)]
 pub struct MergeStamp;
 
@@ -218,11 +223,14 @@ impl Step for MergeStamp {
     type Left = StampHeader;
     type Output = StampHeader;
     type Right = StampHeader;
-    /// `(left, merged, right)`, each an `(action_set, tachygram_set)` pair.
+    /// `(left, merged, right, disjointness)`: `left`, `merged` and `right`
+    /// are each an `(action_set, tachygram_set)` pair, and `disjointness`
+    /// shows the left and right tachygram sets share no member.
     type Witness<'source> = (
         (ActionSetPoly, TachygramSetPoly),
         (ActionSetPoly, TachygramSetPoly),
         (ActionSetPoly, TachygramSetPoly),
+        TachygramDisjointness,
     );
 
     const INDEX: Index = Index::new(17);
@@ -234,6 +242,7 @@ impl Step for MergeStamp {
             (left_action_set, left_tachygram_set),
             (merged_action_set, merged_tachygram_set),
             (right_action_set, right_tachygram_set),
+            disjointness,
         ): Self::Witness<'source>,
         (left_action_commit, left_tachygram_commit, left_anchor): <Self::Left as Header>::Data,
         (right_action_commit, right_tachygram_commit, right_anchor): <Self::Right as Header>::Data,
@@ -269,6 +278,8 @@ impl Step for MergeStamp {
 
         let merged_action_set_commit = merged_action_set.commit();
         let merged_tachygram_set_commit = merged_tachygram_set.commit();
+        let left_tachygram_poly: Polynomial = left_tachygram_set.into();
+        let right_tachygram_poly: Polynomial = right_tachygram_set.into();
 
         // The merged sets are witnessed; confirm each is the `left · right`
         // union of its halves via the product-opening relation, never built
@@ -282,12 +293,43 @@ impl Step for MergeStamp {
         )?;
         enforce_poly_product(
             ctx,
-            &left_tachygram_set.into(),
-            &right_tachygram_set.into(),
+            &left_tachygram_poly,
+            &right_tachygram_poly,
             &merged_tachygram_set.into(),
             "MergeStamp: merged tachygram set must be the product of left and right tachygram sets",
         )?;
 
+        // The product alone would accept a repeated tachygram as a double
+        // root. Show the halves share no root: `U·L + V·R = 1` for witnessed
+        // cofactors `U` and `V`, with `L` and `R` bound to the headers above.
+        // The cofactors and products are fresh witnesses, but the claim is
+        // only that some cofactors exist: each product is opened against its
+        // cofactor and set, and both against the constant `1`, built here.
+        enforce_poly_product(
+            ctx,
+            &disjointness.left_cofactor,
+            &left_tachygram_poly,
+            &disjointness.left_product,
+            "MergeStamp: left disjointness product must be the left cofactor times the left tachygram set",
+        )?;
+        enforce_poly_product(
+            ctx,
+            &disjointness.right_cofactor,
+            &right_tachygram_poly,
+            &disjointness.right_product,
+            "MergeStamp: right disjointness product must be the right cofactor times the right tachygram set",
+        )?;
+        enforce_shifted_combination(
+            ctx,
+            [
+                (&disjointness.left_product, 0),
+                (&disjointness.right_product, 0),
+            ],
+            [],
+            &Polynomial::from_coeffs(vec![Fp::ONE]),
+            "MergeStamp: tachygram sets must be disjoint",
+        )?;
+
         Ok((
             (
                 merged_action_set_commit,
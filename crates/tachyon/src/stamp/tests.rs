@@ -1,17 +1,22 @@
 #![allow(clippy::panic, reason = "test code")]
 
-use alloc::{boxed::Box, string::ToString as _, vec, vec::Vec};
+use alloc::{
+    string::{String, ToString as _},
+    vec,
+    vec::Vec,
+};
 
 use ff::Field as _;
 use rand::{SeedableRng as _, rngs::StdRng};
 
 use super::*;
 use crate::{
-    action,
-    constants::EPOCH_SIZE,
+    action, bundle,
+    constants::{EPOCH_SIZE, MAX_BLOCK_SIZE},
     fixtures::{
-        PoolSim, WalletSim, build_autonome, build_output_stamp, forge_overlapping_merge,
-        random_action, random_block, random_block_with, shared_sk, spend_witness,
+        PoolSim, WalletSim, build_anchor_chain_pcd, build_autonome, build_output_stamp,
+        forge_overlapping_merge, random_action, random_block, random_block_with, shared_sk,
+        spend_witness,
     },
     primitives::BlockHeight,
 };
@@ -49,8 +54,8 @@ fn merge_stamp_iff_matching_anchors() {
 
         let result = ProofStamp::merge(
             rng,
-            (stamp_a, BTreeSet::from_iter([plan_a.descriptor()])),
-            (stamp_b, BTreeSet::from_iter([plan_b.descriptor()])),
+            (stamp_a, &BTreeSet::from_iter([plan_a.descriptor()])),
+            (stamp_b, &BTreeSet::from_iter([plan_b.descriptor()])),
         );
         assert_eq!(
             result.is_ok(),
@@ -131,6 +136,25 @@ fn plan_prove_rejects_invalid_inputs() {
         );
     }
 
+    // Descriptor swap: each witness is paired with the other spend's
+    // descriptor, caught before any proving.
+    {
+        let swapped = alloc::vec![
+            (plan_b.descriptor(), alpha_a, note_a, rcv_a),
+            (plan_a.descriptor(), alpha_b, note_b, rcv_b),
+        ];
+        let plan = Plan::new(swapped, alloc::vec![], anchor);
+        let pcds = alloc::vec![bundle_a(), bundle_b()];
+        let err = plan.prove(rng, &user.pak, pcds).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ProveError::WitnessMismatch(action::WitnessMismatch::ValueCommitment)
+            ),
+            "expected WitnessMismatch(ValueCommitment)"
+        );
+    }
+
     // Correspondence swap: lengths match, pairing is wrong. SpendBind's
     // `spendable.cm == note.commitment()` check rejects the mismatched lineage.
     {
@@ -147,6 +171,162 @@ fn plan_prove_rejects_invalid_inputs() {
     }
 }
 
+/// A spend whose lineage ends at an older anchor is lifted to the plan anchor
+/// when a matching anchor chain is supplied, and rejected otherwise. Its two
+/// leaf steps and the lift are each reported as a step.
+#[test]
+fn prove_lifted_advances_stale_spend() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let user = WalletSim::new(shared_sk());
+    let mut pool = PoolSim::genesis(rng);
+
+    let note = user.random_note(500);
+    pool.mine(random_block_with(rng, &[vec![note.commitment()]], 50));
+    let spend_height = pool.height();
+    let spendable = user.fresh_spend(rng, &pool, spend_height, &note);
+    let range = user.derived_range(rng, &note, spend_height.epoch(), 2);
+
+    pool.advance(2, |_| random_block(rng, 1, 4));
+    let plan_height = pool.height();
+    assert_eq!(spend_height.epoch(), plan_height.epoch());
+    let plan_anchor = pool.anchor_at(plan_height);
+    assert_ne!(plan_anchor, pool.anchor_at(spend_height));
+
+    let (rcv, theta, alpha) = spend_witness(rng, &note);
+    let spend = action::Plan::spend(note, theta, rcv, |randomizer| {
+        user.pak.ak.derive_action_public(&randomizer)
+    });
+    let plan = Plan::new(
+        alloc::vec![(spend.descriptor(), alpha, note, rcv)],
+        alloc::vec![],
+        plan_anchor,
+    );
+
+    let err = plan
+        .clone()
+        .prove(
+            rng,
+            &user.pak,
+            alloc::vec![(range.clone(), spendable.clone())],
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, ProveError::AnchorUnreachable),
+        "expected AnchorUnreachable"
+    );
+
+    let chain = build_anchor_chain_pcd(rng, &pool, spend_height.next().unwrap()..=plan_height);
+    let stamp = plan
+        .clone()
+        .prove_lifted(
+            rng,
+            &user.pak,
+            alloc::vec![(range.clone(), spendable.clone())],
+            alloc::vec![chain.clone()],
+        )
+        .expect("lifted spend proves");
+    assert_eq!(stamp.anchor, plan_anchor);
+
+    // SpendBind and SpendStamp are a step each, and the lift one more.
+    assert_eq!(
+        plan.cost(),
+        ProvingCost {
+            steps: 2,
+            rerandomizations: 1,
+        }
+    );
+    let mut seen = Vec::new();
+    plan.prove_observed(
+        rng,
+        &user.pak,
+        alloc::vec![(range, spendable)],
+        alloc::vec![chain],
+        |progress| {
+            seen.push((progress.proven, progress.total));
+            ControlFlow::Continue(())
+        },
+    )
+    .expect("lifted spend proves");
+    assert_eq!(seen, [(1, 3), (2, 3), (3, 3)]);
+}
+
+/// Proving reports each PCD step in turn, as many as the plan's cost
+/// predicts, and stops when told to.
+#[test]
+fn prove_observed_reports_and_cancels() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (_, plan_a) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    let (_, plan_b) = build_output_stamp(rng, anchor, wallet.random_note(300));
+    let plan = bundle::Plan::new(vec![], vec![plan_a, plan_b]).stamp_plan(anchor);
+
+    assert_eq!(
+        plan.cost(),
+        ProvingCost {
+            steps: 3,
+            rerandomizations: 3,
+        }
+    );
+
+    let mut seen = Vec::new();
+    let stamp = plan
+        .clone()
+        .prove_observed(rng, &wallet.pak, vec![], vec![], |progress| {
+            seen.push((progress.proven, progress.total));
+            ControlFlow::Continue(())
+        })
+        .expect("prove");
+    assert_eq!(seen, [(1, 3), (2, 3), (3, 3)]);
+    assert!(stamp.is_covering(plan.descriptors()));
+
+    let finished = plan
+        .clone()
+        .prove_observed(rng, &wallet.pak, vec![], vec![], |progress| {
+            if progress.proven == progress.total {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .expect("breaking on the last report keeps the stamp");
+    assert!(finished.is_covering(plan.descriptors()));
+
+    let err = plan
+        .prove_observed(rng, &wallet.pak, vec![], vec![], |progress| {
+            if progress.proven == 1 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap_err();
+    assert!(matches!(err, ProveError::Cancelled), "expected Cancelled");
+}
+
+/// The same plan proven from the same seed gives the same stamp bytes.
+#[test]
+fn proving_is_reproducible_from_a_seed() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (_, plan_a) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    let (_, plan_b) = build_output_stamp(rng, anchor, wallet.random_note(300));
+    let plan = bundle::Plan::new(vec![], vec![plan_a, plan_b]).stamp_plan(anchor);
+
+    let encode = |seed| {
+        let stamp = plan
+            .clone()
+            .prove(&mut StdRng::seed_from_u64(seed), &wallet.pak, vec![])
+            .expect("prove");
+        let mut buf = Vec::new();
+        stamp.write(&mut buf).expect("write");
+        buf
+    };
+    assert_eq!(encode(7), encode(7), "same seed, same stamp");
+    assert_ne!(encode(7), encode(8), "another seed, another proof");
+}
+
 /// `merge` populates `covered_actions` with the covered-actions digest of
 /// the merged descriptor list, order-independently.
 #[test]
@@ -167,8 +347,8 @@ fn merge_populates_covered_actions() {
 
     let merged = ProofStamp::merge(
         rng,
-        (stamp_a, BTreeSet::from_iter([plan_a.descriptor()])),
-        (stamp_b, BTreeSet::from_iter([plan_b.descriptor()])),
+        (stamp_a, &BTreeSet::from_iter([plan_a.descriptor()])),
+        (stamp_b, &BTreeSet::from_iter([plan_b.descriptor()])),
     )
     .expect("merge");
     // `merge` sorts the concatenated descriptors into canonical order, so the
@@ -176,6 +356,285 @@ fn merge_populates_covered_actions() {
     assert_eq!(merged.coverage, expected);
 }
 
+/// A rerandomized stamp keeps everything but its proof bytes, and still
+/// verifies.
+#[test]
+fn rerandomize_changes_only_the_proof() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (stamp, plan) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    let descriptor = plan.descriptor();
+
+    let rerandomized = stamp
+        .clone()
+        .rerandomize(rng, &BTreeSet::from_iter([descriptor]))
+        .expect("rerandomize");
+    assert_eq!(rerandomized.coverage, stamp.coverage);
+    assert_eq!(rerandomized.anchor, stamp.anchor);
+    assert_eq!(rerandomized.tachygrams, stamp.tachygrams);
+    assert_ne!(
+        rerandomized.proof.serialize().as_ref(),
+        stamp.proof.serialize().as_ref(),
+        "proof bytes are unlinkable"
+    );
+    assert_ne!(rerandomized.stamp_digest(), stamp.stamp_digest());
+    assert!(
+        rerandomized
+            .verify_proof(rng, [descriptor.digest().expect("digest")])
+            .expect("verify"),
+        "rerandomized proof verifies"
+    );
+}
+
+/// Any number of stamps merge into one covering all their actions, and
+/// none merge into nothing.
+#[test]
+fn merge_all_covers_every_stamp() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let parts: Vec<(ProofStamp, action::Plan<effect::Output>)> = [100, 200, 300]
+        .into_iter()
+        .map(|value| build_output_stamp(rng, anchor, wallet.random_note(value)))
+        .collect();
+    let descriptors: BTreeSet<action::Descriptor> =
+        parts.iter().map(|part| part.1.descriptor()).collect();
+    let tachygrams: BTreeSet<Tachygram> = parts
+        .iter()
+        .flat_map(|part| part.0.tachygrams.iter().copied())
+        .collect();
+
+    let merged = ProofStamp::merge_all(
+        rng,
+        parts
+            .into_iter()
+            .map(|(stamp, plan)| (stamp, BTreeSet::from_iter([plan.descriptor()]))),
+    )
+    .expect("merge all");
+    assert!(merged.is_covering(descriptors.iter().copied()));
+    assert_eq!(merged.tachygrams, tachygrams);
+    assert!(
+        merged
+            .verify_proof(
+                rng,
+                descriptors
+                    .iter()
+                    .map(|desc| desc.digest().expect("digest"))
+            )
+            .expect("verify"),
+        "merged proof verifies"
+    );
+
+    let err = ProofStamp::merge_all(rng, []).expect_err("nothing to merge");
+    assert!(matches!(err, ProveError::NoActions));
+}
+
+/// Pieces proven apart survive the wire, are checked as they arrive, and
+/// assemble into one stamp covering them all.
+#[test]
+fn partial_assembles_pieces() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (stamp_a, plan_a) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    let (stamp_b, plan_b) = build_output_stamp(rng, anchor, wallet.random_note(300));
+    let covered_a = BTreeSet::from_iter([plan_a.descriptor()]);
+    let covered_b = BTreeSet::from_iter([plan_b.descriptor()]);
+
+    let mut partial = Partial::new();
+    partial
+        .add(stamp_a.clone(), covered_a.clone())
+        .expect("first piece");
+    assert_eq!(
+        partial.add(stamp_b.clone(), covered_a.clone()),
+        Err(PartialError::NotCovering)
+    );
+    assert_eq!(
+        partial.add(stamp_a.clone(), covered_a.clone()),
+        Err(PartialError::SharedAction)
+    );
+
+    let mut other = Partial::new();
+    other.add(stamp_b, covered_b.clone()).expect("second piece");
+    let mut buf = Vec::new();
+    other.write(&mut buf).expect("write");
+    let other = Partial::read(&*buf).expect("read");
+    partial.combine(other).expect("combine");
+    assert_eq!(partial.len(), 2);
+    assert_eq!(
+        partial.covered(),
+        covered_a.union(&covered_b).copied().collect()
+    );
+
+    let stamp = partial.finish(rng).expect("assemble");
+    assert!(stamp.is_covering([plan_a.descriptor(), plan_b.descriptor()]));
+    assert!(
+        stamp
+            .verify_proof(
+                rng,
+                [plan_a.descriptor(), plan_b.descriptor()]
+                    .iter()
+                    .map(|desc| desc.digest().expect("digest"))
+            )
+            .expect("verify"),
+        "assembled proof verifies"
+    );
+}
+
+/// Stamps at different anchors merge at the later one, whichever side is
+/// older, and not at all without a joining segment.
+#[test]
+fn merge_lifting_takes_the_later_anchor() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let mut pool = PoolSim::genesis(rng);
+    pool.mine(random_block(rng, 1, 2));
+    let old_height = pool.height();
+    pool.advance(2, |_| random_block(rng, 1, 2));
+    let new_height = pool.height();
+    let (old_anchor, new_anchor) = (pool.anchor_at(old_height), pool.anchor_at(new_height));
+    let chain = build_anchor_chain_pcd(rng, &pool, old_height.next().unwrap()..=new_height);
+
+    let (old_stamp, old_plan) = build_output_stamp(rng, old_anchor, wallet.random_note(200));
+    let (new_stamp, new_plan) = build_output_stamp(rng, new_anchor, wallet.random_note(300));
+    let old = (old_stamp, BTreeSet::from_iter([old_plan.descriptor()]));
+    let new = (new_stamp, BTreeSet::from_iter([new_plan.descriptor()]));
+
+    for (left, right) in [(&old, &new), (&new, &old)] {
+        let merged = ProofStamp::merge_lifting(
+            rng,
+            (left.0.clone(), &left.1),
+            (right.0.clone(), &right.1),
+            &[chain.clone()],
+        )
+        .expect("the older stamp is lifted");
+        assert_eq!(merged.anchor, new_anchor);
+        assert!(
+            merged
+                .verify_proof(
+                    rng,
+                    [old_plan.descriptor(), new_plan.descriptor()]
+                        .iter()
+                        .map(|desc| desc.digest().expect("digest"))
+                )
+                .expect("verify"),
+            "merged proof verifies"
+        );
+    }
+
+    let err = ProofStamp::merge_lifting(rng, (old.0, &old.1), (new.0, &new.1), &[])
+        .expect_err("no joining segment");
+    assert!(matches!(err, ProveError::AnchorUnreachable));
+}
+
+/// A merged stamp publishes every tachygram of its parts exactly once, in
+/// canonical order, so its encoding reads back unchanged.
+#[test]
+fn merge_keeps_tachygrams_canonical() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (stamp_a, plan_a) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    let (stamp_b, plan_b) = build_output_stamp(rng, anchor, wallet.random_note(300));
+    let parts = stamp_a.tachygrams.len() + stamp_b.tachygrams.len();
+    let union: BTreeSet<Tachygram> = stamp_a
+        .tachygrams
+        .union(&stamp_b.tachygrams)
+        .copied()
+        .collect();
+
+    let merged = ProofStamp::merge(
+        rng,
+        (stamp_b, &BTreeSet::from_iter([plan_b.descriptor()])),
+        (stamp_a, &BTreeSet::from_iter([plan_a.descriptor()])),
+    )
+    .expect("merge");
+    assert_eq!(merged.tachygrams, union);
+    assert_eq!(merged.tachygrams.len(), parts, "no tachygram was dropped");
+
+    let mut buf = Vec::new();
+    merged.write(&mut buf).expect("write");
+    let decoded = ProofStamp::read(&*buf).expect("merged stamp is canonical");
+    assert_eq!(decoded.tachygrams, merged.tachygrams);
+}
+
+/// Assert [`ProofStamp::merge`] refuses two stamps for a shared tachygram,
+/// as does [`ProofStamp::merge_lifting`].
+fn assert_tachygram_collision(
+    rng: &mut StdRng,
+    left: (&ProofStamp, &BTreeSet<action::Descriptor>),
+    right: (&ProofStamp, &BTreeSet<action::Descriptor>),
+) {
+    let shared = *left.0.collisions(right.0).first().expect("stamps collide");
+    let merge_err = ProofStamp::merge(rng, (left.0.clone(), left.1), (right.0.clone(), right.1))
+        .expect_err("overlapping tachygrams must not merge");
+    assert!(
+        matches!(merge_err, ProveError::TachygramCollision(tachygram) if tachygram == shared),
+        "expected TachygramCollision, got {merge_err:?}"
+    );
+    let lifting_err = ProofStamp::merge_lifting(
+        rng,
+        (left.0.clone(), left.1),
+        (right.0.clone(), right.1),
+        &[],
+    )
+    .expect_err("overlapping tachygrams must not merge");
+    assert!(
+        matches!(lifting_err, ProveError::TachygramCollision(tachygram) if tachygram == shared),
+        "expected TachygramCollision, got {lifting_err:?}"
+    );
+}
+
+/// Prove the merge step directly, skipping the collision check, and return
+/// the constraint it fails on.
+fn merge_step_error(
+    rng: &mut StdRng,
+    left: (&ProofStamp, &BTreeSet<action::Descriptor>),
+    right: (&ProofStamp, &BTreeSet<action::Descriptor>),
+) -> String {
+    let components = |stamp: &ProofStamp, descriptors: &BTreeSet<action::Descriptor>| {
+        (
+            descriptors
+                .iter()
+                .map(|desc| desc.digest().expect("action digest"))
+                .collect(),
+            stamp.tachygrams.clone(),
+            stamp.anchor,
+            stamp.proof.clone(),
+        )
+    };
+    let merge_err = ProofStamp::prove_merge(
+        rng,
+        components(left.0, left.1),
+        components(right.0, right.1),
+    )
+    .expect_err("the step must refuse the overlap");
+    let ragu::Error::InvalidWitness(inner) = merge_err else {
+        panic!("expected InvalidWitness, got {merge_err:?}");
+    };
+    inner.to_string()
+}
+
+/// Fuse the merge step over the multiset union of both sides, as a dishonest
+/// prover would, and return the constraint it fails on.
+fn forged_merge_error(
+    rng: &mut StdRng,
+    left: (&ProofStamp, &BTreeSet<action::Descriptor>),
+    right: (&ProofStamp, &BTreeSet<action::Descriptor>),
+) -> String {
+    let forge_err = forge_overlapping_merge(
+        rng,
+        (left.0, &Vec::from_iter(left.1.iter().copied())),
+        (right.0, &Vec::from_iter(right.1.iter().copied())),
+    )
+    .expect_err("the step must refuse the forged overlap");
+    let ragu::Error::InvalidWitness(inner) = forge_err else {
+        panic!("expected InvalidWitness, got {forge_err:?}");
+    };
+    inner.to_string()
+}
+
 /// Reusing a note as an output collides on the note commitment: each
 /// `OutputStamp`'s sole tachygram is that commitment. The nullifier-side analog
 /// is [`double_spend_cannot_aggregate`] — both reuse modes are caught the same
@@ -206,59 +665,35 @@ fn double_output_cannot_aggregate() {
     let descriptors_a = BTreeSet::from_iter([plan_a.descriptor()]);
     let descriptors_b = BTreeSet::from_iter([plan_b.descriptor()]);
 
-    // The honest merge refuses the overlap on the tachygram-set product relation.
-    {
-        let merge_err = ProofStamp::merge(
-            rng,
-            (stamp_a.clone(), descriptors_a.clone()),
-            (stamp_b.clone(), descriptors_b.clone()),
-        )
-        .expect_err("overlapping tachygrams must not merge");
-        let ProveError::MergeFailed(ragu::Error::InvalidWitness(inner)) = merge_err else {
-            panic!("expected MergeFailed(InvalidWitness), got {merge_err:?}");
-        };
-        assert_eq!(
-            inner.to_string(),
-            "MergeStamp: merged tachygram set must be the product of left and right tachygram sets"
-        );
-    }
-
-    let evil_pcd = forge_overlapping_merge(
-        rng,
-        (&stamp_a, &Vec::from_iter(descriptors_a.clone())),
-        (&stamp_b, &Vec::from_iter(descriptors_b.clone())),
+    // The collision is visible before any merge is attempted.
+    assert_eq!(stamp_a.collisions(&stamp_b), stamp_a.tachygrams);
+    let (stamp_c, _plan_c) = build_output_stamp(rng, anchor, wallet.random_note(200));
+    assert!(
+        stamp_a.collisions(&stamp_c).is_empty(),
+        "distinct notes do not collide"
+    );
+    assert_eq!(
+        duplicate_tachygrams([&stamp_a, &stamp_c, &stamp_b]),
+        stamp_a.tachygrams
+    );
+    assert!(
+        duplicate_tachygrams([&stamp_a, &stamp_c]).is_empty(),
+        "distinct stamps have no duplicates"
     );
 
-    let all_descriptors: Vec<action::Descriptor> =
-        descriptors_a.union(&descriptors_b).copied().collect();
+    // The honest merge refuses the overlap before proving, and the step
+    // refuses it on the tachygram-set product relation.
+    assert_tachygram_collision(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b));
+    assert_eq!(
+        merge_step_error(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b)),
+        "MergeStamp: merged tachygram set must be the product of left and right tachygram sets"
+    );
 
-    // Publish the forgery as a coherent stamp: canonical deduplicated tachygrams
-    // and the covered-actions digest over the merged descriptors, so `covers`
-    // accepts it and only proof verification rejects it.
-    let coverage = {
-        let mut desc_bytes: Vec<[u8; 64]> = all_descriptors.iter().copied().collect();
-        desc_bytes.sort_unstable();
-        blake2b::action_descriptor_digest(&desc_bytes)
-    };
-    let stamp = ProofStamp {
-        coverage,
-        anchor: evil_pcd.data().2,
-        tachygrams: stamp_a
-            .tachygrams
-            .union(&stamp_b.tachygrams)
-            .copied()
-            .collect(),
-        proof: Box::new(evil_pcd.proof().clone()),
-    };
-    let digests: Vec<ActionDigest> = all_descriptors
-        .iter()
-        .map(|desc| desc.digest().expect("action digest"))
-        .collect();
-    assert!(
-        !stamp
-            .verify_proof(rng, digests)
-            .expect("proof system verification"),
-        "multiset-backed proof must not verify against the deduplicated set"
+    // A forged merge over the multiset union satisfies both products, but
+    // not the disjointness of the tachygram sets.
+    assert_eq!(
+        forged_merge_error(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b)),
+        "MergeStamp: tachygram sets must be disjoint"
     );
 }
 
@@ -327,71 +762,28 @@ fn double_spend_cannot_aggregate() {
         "same-note spends share their nullifiers"
     );
 
-    // The honest merge refuses the overlap on the tachygram-set product relation.
-    {
-        let merge_err = ProofStamp::merge(
-            rng,
-            (stamp_a.clone(), descriptors_a.clone()),
-            (stamp_b.clone(), descriptors_b.clone()),
-        )
-        .expect_err("shared nullifiers must not merge");
-        let ProveError::MergeFailed(ragu::Error::InvalidWitness(inner)) = merge_err else {
-            panic!("expected MergeFailed(InvalidWitness), got {merge_err:?}");
-        };
-        assert_eq!(
-            inner.to_string(),
-            "MergeStamp: merged tachygram set must be the product of left and right tachygram sets"
-        );
-    }
-
-    let evil_pcd = forge_overlapping_merge(
-        rng,
-        (&stamp_a, &Vec::from_iter(descriptors_a.clone())),
-        (&stamp_b, &Vec::from_iter(descriptors_b.clone())),
+    // The honest merge refuses the overlap before proving, and the step
+    // refuses it on the tachygram-set product relation.
+    assert_tachygram_collision(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b));
+    assert_eq!(
+        merge_step_error(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b)),
+        "MergeStamp: merged tachygram set must be the product of left and right tachygram sets"
     );
 
-    let all_descriptors: Vec<action::Descriptor> =
-        descriptors_a.union(&descriptors_b).copied().collect();
-
-    // Publish the forgery as a coherent stamp: the covered-actions digest over
-    // the merged descriptors, and the canonical deduplicated nullifier set that
-    // cannot reconstruct the doubled multiset the proof commits to.
-    let coverage = {
-        let mut desc_bytes: Vec<[u8; 64]> = all_descriptors.iter().copied().collect();
-        desc_bytes.sort_unstable();
-        blake2b::action_descriptor_digest(&desc_bytes)
-    };
-    let stamp = ProofStamp {
-        coverage,
-        anchor: evil_pcd.data().2,
-        tachygrams: stamp_a
-            .tachygrams
-            .union(&stamp_b.tachygrams)
-            .copied()
-            .collect(),
-        proof: Box::new(evil_pcd.proof().clone()),
-    };
-
-    let digests: Vec<ActionDigest> = all_descriptors
-        .iter()
-        .map(|desc| desc.digest().expect("action digest"))
-        .collect();
-    assert!(
-        !stamp
-            .verify_proof(rng, digests)
-            .expect("proof system verification"),
-        "doubled-nullifier proof must not verify"
+    // A forged merge over the multiset union satisfies both products, but
+    // not the disjointness of the tachygram sets.
+    assert_eq!(
+        forged_merge_error(rng, (&stamp_a, &descriptors_a), (&stamp_b, &descriptors_b)),
+        "MergeStamp: tachygram sets must be disjoint"
     );
 }
 
 /// A stamp cannot cover the same action twice. The honest merge refuses it on
 /// the action-set product relation (the two contributors share the descriptor).
 /// A forced-fuse forgery bypasses that, but every action carries a tachygram,
-/// so the doubled action doubles its tachygram: verifying against `[d, d]`
-/// matches the action accumulator while the published deduplicated tachygram
-/// set fails to reconstruct the doubled one, so the proof is `Disproved`. The
-/// wire-level duplicate tachygram is caught by
-/// [`read_rejects_duplicate_tachygrams`].
+/// so the doubled action doubles its tachygram, and the step refuses to merge
+/// tachygram sets that are not disjoint. The wire-level duplicate tachygram is
+/// caught by [`read_rejects_duplicate_tachygrams`].
 #[test]
 fn cannot_forge_stamp_covering_duplicated_action() {
     let rng = &mut StdRng::seed_from_u64(0);
@@ -404,63 +796,33 @@ fn cannot_forge_stamp_covering_duplicated_action() {
     let (output_stamp, plan) = build_output_stamp(rng, anchor, note);
     let descriptors = BTreeSet::from_iter([plan.descriptor()]);
 
-    // The honest merge refuses the shared action on the action-set product
-    // relation (checked before the tachygram product).
-    {
-        let merge_err = ProofStamp::merge(
-            rng,
-            (output_stamp.clone(), descriptors.clone()),
-            (output_stamp.clone(), descriptors.clone()),
-        )
-        .expect_err("a duplicated action must not merge");
-        let ProveError::MergeFailed(ragu::Error::InvalidWitness(inner)) = merge_err else {
-            panic!("expected MergeFailed(InvalidWitness), got {merge_err:?}");
-        };
-        assert_eq!(
-            inner.to_string(),
-            "MergeStamp: merged action set must be the product of left and right action sets"
-        );
-    }
-
-    let evil_pcd = forge_overlapping_merge(
+    // The honest merge refuses the shared tachygram before proving, and the
+    // step refuses the shared action on the action-set product relation
+    // (checked before the tachygram product).
+    assert_tachygram_collision(
         rng,
-        (&output_stamp, &Vec::from_iter(descriptors.clone())),
-        (&output_stamp, &Vec::from_iter(descriptors.clone())),
+        (&output_stamp, &descriptors),
+        (&output_stamp, &descriptors),
+    );
+    assert_eq!(
+        merge_step_error(
+            rng,
+            (&output_stamp, &descriptors),
+            (&output_stamp, &descriptors)
+        ),
+        "MergeStamp: merged action set must be the product of left and right action sets"
     );
 
-    let all_descriptors: Vec<action::Descriptor> = descriptors
-        .iter()
-        .chain(descriptors.iter())
-        .copied()
-        .collect();
-
-    // Publish the forgery as a coherent stamp: the covered-actions digest over
-    // the duplicated action, and the canonical deduplicated tachygram set that
-    // cannot reconstruct the doubled multiset the proof commits to.
-    let coverage = {
-        let mut desc_bytes: Vec<[u8; 64]> = all_descriptors.iter().copied().collect();
-        desc_bytes.sort_unstable();
-        blake2b::action_descriptor_digest(&desc_bytes)
-    };
-    let stamp = ProofStamp {
-        coverage,
-        anchor: evil_pcd.data().2,
-        tachygrams: output_stamp.tachygrams.iter().copied().collect(),
-        proof: Box::new(evil_pcd.proof().clone()),
-    };
-
-    // Verifying against the doubled descriptors makes the action accumulator
-    // match; only the deduplicated tachygram set fails to reconstruct the
-    // doubled tachygram the proof commits to.
-    let digests: Vec<ActionDigest> = all_descriptors
-        .iter()
-        .map(|desc| desc.digest().expect("action digest"))
-        .collect();
-    assert!(
-        !stamp
-            .verify_proof(rng, digests)
-            .expect("proof system verification"),
-        "a stamp covering a duplicated action must not verify"
+    // A forged merge over the multiset union doubles the action and its
+    // tachygram alike, which satisfies both products, but a tachygram shared
+    // by both sides fails their disjointness.
+    assert_eq!(
+        forged_merge_error(
+            rng,
+            (&output_stamp, &descriptors),
+            (&output_stamp, &descriptors)
+        ),
+        "MergeStamp: tachygram sets must be disjoint"
     );
 }
 
@@ -497,6 +859,29 @@ fn verify_cannot_distinguish_a_deduplicated_duplicate() {
     );
 }
 
+/// Tachygrams are bound by the proof: attaching one that no action derived
+/// fails verification.
+#[test]
+fn verify_rejects_attached_tachygram() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let pool = PoolSim::genesis(rng);
+
+    let note = wallet.random_note(200);
+    let (mut stamp, plan) = build_output_stamp(rng, pool.anchor(), note);
+    let digest = plan.descriptor().digest().expect("action digest");
+
+    stamp
+        .tachygrams
+        .insert(Tachygram::from(Fp::random(&mut *rng)));
+    assert!(
+        !stamp
+            .verify_proof(rng, [digest])
+            .expect("proof system verification"),
+        "an attached tachygram must not verify"
+    );
+}
+
 /// `verify_proof` reconstructs the action polynomial from the action digests it
 /// is given, as a multiset: the exact covered actions verify (in any order),
 /// and any deviation — a dropped, duplicated, extra, or substituted action —
@@ -616,6 +1001,126 @@ fn read_accepts_distinct_sorted_tachygrams() {
     ProofStamp::read(&*buf).expect("distinct sorted tachygrams must be accepted");
 }
 
+/// A tachygram count no block could hold is rejected before any tachygram
+/// is read.
+#[test]
+fn read_rejects_oversized_tachygram_count() {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&[0u8; 32]); // covered actions digest
+    Anchor(Fp::ZERO).write(&mut buf).expect("write anchor");
+    serialization::write_compactsize(
+        &mut buf,
+        u64::try_from(MAX_STAMP_TACHYGRAMS + 1).expect("fits u64"),
+    )
+    .expect("write tachygram count");
+
+    let err = ProofStamp::read(&*buf).expect_err("oversized count must be rejected");
+    assert_eq!(err.to_string(), "too many tachygrams");
+}
+
+/// The largest stamp `read` accepts fits in a block, and one more tachygram
+/// would not.
+#[test]
+fn max_stamp_tachygrams_fits_a_block() {
+    assert!(ProofStamp::estimated_serialized_size(MAX_STAMP_TACHYGRAMS) <= MAX_BLOCK_SIZE);
+    assert!(ProofStamp::estimated_serialized_size(MAX_STAMP_TACHYGRAMS + 1) > MAX_BLOCK_SIZE);
+}
+
+/// A stamp's encoding is exact: re-encoding what was read reproduces the
+/// bytes, and any truncation fails.
+#[test]
+fn stamp_encoding_is_strict() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let pool = PoolSim::genesis(rng);
+    let (stamp, _plan) = build_output_stamp(rng, pool.anchor(), wallet.random_note(200));
+
+    let mut buf = Vec::new();
+    stamp.write(&mut buf).expect("write");
+    assert_eq!(buf.len(), stamp.encoded_len());
+
+    let decoded = ProofStamp::read(&*buf).expect("read");
+    let mut rewritten = Vec::new();
+    decoded.write(&mut rewritten).expect("write");
+    assert_eq!(rewritten, buf);
+
+    for len in [0, 32, 64, 65, buf.len() - 1] {
+        ProofStamp::read(&buf[..len]).expect_err("truncated stamp must be rejected");
+    }
+}
+
+/// A stamp verifies on its own against exactly the actions it covers.
+#[test]
+fn stamp_verifies_against_its_actions() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::new(shared_sk());
+    let autonome = build_autonome(rng, &wallet, 1000, 700);
+    let actions = autonome.actions.as_slice();
+
+    autonome
+        .stamp
+        .verify(rng, actions)
+        .expect("stamp covers its actions");
+    autonome
+        .stamp
+        .verify_covering(rng, [&actions[..1], &actions[1..]])
+        .expect("split across bundles, the actions are the same");
+
+    let err = autonome
+        .stamp
+        .verify(rng, &actions[..1])
+        .expect_err("a missing action changes the coverage");
+    assert!(matches!(
+        err,
+        VerificationError::Coverage(VerifyCoverageError::StampActionsMismatch)
+    ));
+
+    let err = autonome
+        .stamp
+        .verify_covering(rng, [actions, &actions[..1]])
+        .expect_err("an action may be covered only once");
+    assert!(matches!(
+        err,
+        VerificationError::Coverage(VerifyCoverageError::DuplicateActions)
+    ));
+}
+
+/// An envelope round-trips its stamp, skips trailing fields, and leaves a
+/// version or required flag it does not know unopened.
+#[test]
+fn envelope_is_forward_compatible() {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let wallet = WalletSim::random(rng);
+    let anchor = PoolSim::genesis(rng).anchor();
+    let (stamp, _plan) = build_output_stamp(rng, anchor, wallet.random_note(200));
+
+    let mut envelope = Envelope::seal(&stamp).expect("seal");
+    let mut buf = Vec::new();
+    envelope.write(&mut buf).expect("write");
+    let read = Envelope::read(&*buf).expect("read");
+    assert_eq!(read, envelope);
+    let opened = read.open().expect("open").expect("known version");
+    assert_eq!(opened.tachygrams, stamp.tachygrams);
+    assert_eq!(opened.stamp_digest(), stamp.stamp_digest());
+
+    envelope.payload.extend_from_slice(&[0xAA; 8]);
+    envelope.flags = 0x80;
+    let opened = envelope
+        .open()
+        .expect("open")
+        .expect("optional flag ignored");
+    assert_eq!(opened.stamp_digest(), stamp.stamp_digest());
+
+    envelope.flags = 0x01;
+    assert!(envelope.open().expect("open").is_none());
+    envelope.flags = 0;
+    envelope.version = Envelope::VERSION + 1;
+    let mut buf = Vec::new();
+    envelope.write(&mut buf).expect("write");
+    let unknown = Envelope::read(&*buf).expect("any version reads");
+    assert!(unknown.open().expect("open").is_none());
+}
+
 /// `hStampActionsTachyon` survives a `write`/`read` round-trip.
 #[test]
 fn covered_actions_round_trip() {
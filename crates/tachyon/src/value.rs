@@ -2,6 +2,7 @@
 
 use core::{cmp, ops};
 
+use corez::io::{self, Read, Write};
 use derive_more::{Add, Debug, Display, Eq as TotalEq, Error, From, Into, PartialEq, Sub, Sum};
 use ff::Field as _;
 use group::Curve as _;
@@ -9,7 +10,7 @@ use lazy_static::lazy_static;
 use pasta_curves::{Ep, EpAffine, Fq, arithmetic::CurveExt as _};
 use rand_core::{CryptoRng, RngCore};
 
-use crate::constants::MAX_MONEY;
+use crate::{constants::MAX_MONEY, serialization};
 
 /// Alias for [`ValueTrapdoor`].
 pub type Trapdoor = ValueTrapdoor;
@@ -58,6 +59,16 @@ impl Trapdoor {
         Self(Fq::random(rng))
     }
 
+    /// Read a trapdoor as a canonical scalar.
+    pub(crate) fn read<R: Read>(reader: R) -> io::Result<Self> {
+        Ok(Self(serialization::read_fq(reader)?))
+    }
+
+    /// Write a trapdoor as a canonical scalar.
+    pub(crate) fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        serialization::write_fq(writer, &self.0)
+    }
+
     /// Commit to a given value with this trapdoor.
     ///
     /// $$\mathsf{cv} = \[v\]\,\mathcal{V} + \[\mathsf{rcv}\]\,\mathcal{R}$$
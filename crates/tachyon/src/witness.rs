@@ -13,7 +13,8 @@ use ragu::{Header, Step};
 use crate::{
     note::Nullifier,
     primitives::{
-        ActionDigest, ActionSetPoly, Anchor, EpochIndex, NfSeqPoly, Tachygram, TachygramSetPoly,
+        ActionDigest, ActionSetPoly, Anchor, EpochIndex, NfSeqPoly, Tachygram,
+        TachygramDisjointness, TachygramSetPoly,
     },
     stamp::proof::{
         delegation::NullifierFuse,
@@ -148,7 +149,8 @@ pub fn anchor_seed(
 }
 
 /// Prepare the witness for [`MergeStamp`]: `((left_action_set, left_tg_set),
-/// (merged_action_set, merged_tg_set), (right_action_set, right_tg_set))`.
+/// (merged_action_set, merged_tg_set), (right_action_set, right_tg_set),
+/// disjointness)`.
 #[must_use]
 pub fn merge_stamp(
     (_left, _right): (StepLeft<MergeStamp>, StepRight<MergeStamp>),
@@ -177,5 +179,6 @@ pub fn merge_stamp(
             right_actions.iter().copied().collect::<ActionSetPoly>(),
             right_tgs.iter().copied().collect::<TachygramSetPoly>(),
         ),
+        TachygramDisjointness::new(left_tgs.iter().copied(), right_tgs.iter().copied()),
     )
 }